@@ -13,19 +13,111 @@ pub enum GameSessionInitializeError {
     #[msg("Cannot initialize, players not unique")]
     PlayersNotUnique,
     #[msg("Termination fee transfer failed")]
-    TerminationFeeDepositFailed
+    TerminationFeeDepositFailed,
+    #[msg("Cannot initialize, join deadline is in the past")]
+    JoinDeadlineInPast,
+    #[msg("Cannot initialize, settlement deadline must be after join deadline")]
+    SettlementDeadlineBeforeJoinDeadline,
+    #[msg("Cannot initialize, payout table must split 10000 basis points across at most one share per team")]
+    InvalidPayoutTable,
+    #[msg("Cannot initialize, entry cost per team in base units must divide evenly across its players")]
+    EntryCostNotDivisible,
+    #[msg("Cannot initialize, termination fee rounds down to zero base units")]
+    TerminationFeeRoundsToZero,
+    #[msg("Cannot initialize, termination fee exceeds the pot")]
+    TerminationFeeExceedsPot,
+    #[msg("Cannot initialize, game token account required to pre-fund the termination fee")]
+    GameAccountRequired,
+    #[msg("Cannot initialize, mints with a transfer fee are not supported")]
+    TransferFeeMintNotSupported,
+}
+
+#[error_code]
+pub enum GameSessionStatusError {
+    #[msg("Action not allowed in the current game session status")]
+    InvalidStatus,
+    #[msg("Cannot start, not all players paid")]
+    PlayersNotPaid,
+}
+
+#[error_code]
+pub enum DeclareResultError {
+    #[msg("Result already declared")]
+    ResultAlreadyDeclared,
+    #[msg("Winning team does not exist")]
+    InvalidWinningTeam,
+    #[msg("Settlement deadline has passed")]
+    SettlementDeadlinePassed,
+    #[msg("Placements must rank each team or entrant exactly once")]
+    InvalidPlacements,
+    #[msg("Payout table is not winner takes all, placements required")]
+    PlacementsRequired,
+}
+
+#[error_code]
+pub enum ReplacePlayerError {
+    #[msg("Cannot replace, players not unique")]
+    PlayersNotUnique,
+    #[msg("Player account required to refund a paid seat")]
+    PlayerAccountRequired,
+}
+
+#[error_code]
+pub enum ForfeitError {
+    #[msg("Forfeit is only supported with two teams")]
+    ForfeitRequiresTwoTeams,
+    #[msg("Team does not exist")]
+    InvalidTeam,
+    #[msg("Signer is not a member or captain of the team")]
+    NotTeamMember,
+    #[msg("Captain is not a member of the team")]
+    CaptainNotInTeam,
 }
 
 #[error_code]
 pub enum DepositError {
     #[msg("Player already paid")]
     PlayerAlreadyPaid,
+    #[msg("Join deadline has passed")]
+    JoinDeadlinePassed,
+    #[msg("Player not in game session")]
+    PlayerNotInSession,
+    #[msg("Seat does not exist")]
+    InvalidSeat,
+    #[msg("Seat is not open")]
+    SeatNotOpen,
+    #[msg("Player already seated")]
+    PlayerAlreadySeated,
+    #[msg("Game must co-sign the seat assignment")]
+    GameSignatureRequired,
+    #[msg("Session does not accept variable stakes")]
+    VariableStakesDisabled,
+    #[msg("Stake is below the entry cost")]
+    StakeBelowEntryCost,
+    #[msg("Every seat of the team must be taken and unpaid")]
+    TeamNotPayable,
+    #[msg("Players cannot refer themselves")]
+    InvalidReferrer,
 }
 
 #[error_code]
 pub enum GameSessionCloseError {
     #[msg("Cannot close, players not paid out")]
     PlayersNotPaidOut,
+    #[msg("Cannot close, vault holds less than the session's expected balance")]
+    VaultBalanceTooLow,
+    #[msg("Sponsorship accounts do not match the session's sponsors")]
+    SponsorAccountsMismatch,
+    #[msg("Sponsor account does not belong to the sponsorship")]
+    InvalidSponsorAccount,
+    #[msg("Fee recipient accounts do not match the program config")]
+    FeeRecipientAccountsMismatch,
+    #[msg("Fee recipient account does not belong to the recipient")]
+    InvalidFeeRecipientAccount,
+    #[msg("Cannot close, referrals not claimed before the settlement deadline")]
+    ReferralsNotClaimed,
+    #[msg("Game token account required to return the termination fee deposit")]
+    GameAccountRequired,
 }
 
 #[error_code]
@@ -34,10 +126,94 @@ pub enum TransferError {
     PlayerNotEligibleForRefund,
     #[msg("Player not eligible for payout")]
     PlayerNotEligibleForPayout,
+    #[msg("Player is not in the winning team")]
+    PlayerNotInWinningTeam,
+    #[msg("Result not declared")]
+    ResultNotDeclared,
+    #[msg("Team is not a winning team")]
+    TeamNotWinning,
+    #[msg("Cannot refund the last staked player of a team while the session is running")]
+    LastTeamMemberRefund,
+    #[msg("Refund would leave less staked than the fee and jackpot contribution")]
+    StakeBelowFees,
+}
+
+#[error_code]
+pub enum SponsorError {
+    #[msg("Sponsored amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Sponsorship cannot be refunded")]
+    RefundNotAvailable,
+    #[msg("Session already has the maximum number of sponsorships")]
+    TooManySponsorships,
+    #[msg("A new sponsorship must be at least the entry cost of a seat")]
+    AmountBelowMinimum,
+}
+
+#[error_code]
+pub enum JackpotError {
+    #[msg("Jackpot accounts required to pay the session's contribution")]
+    JackpotAccountsRequired,
+    #[msg("Jackpot account does not belong to the game and mint")]
+    InvalidJackpotAccount,
+    #[msg("Jackpot must be awarded before any winnings are paid out")]
+    PayoutsStarted,
+    #[msg("Jackpot is empty")]
+    JackpotEmpty,
+}
+
+#[error_code]
+pub enum ClaimRefundError {
+    #[msg("Refund cannot be claimed yet")]
+    RefundNotClaimable,
+    #[msg("Winnings cannot be claimed before the settlement deadline")]
+    WinningsNotClaimable,
+}
+
+#[error_code]
+pub enum PlayerAccountsError {
+    #[msg("Player accounts do not match the eligible players")]
+    PlayerAccountsMismatch,
+    #[msg("Player account does not belong to the player")]
+    InvalidPlayerAccount,
+    #[msg("Seat was paid for by another account, its token account is required")]
+    PayerAccountRequired,
 }
 
 #[error_code]
 pub enum ProgramConfigCode {
     #[msg("Fee basis points too high")]
     FeeBasisPointsTooHigh,
+    #[msg("Fee and jackpot basis points too high")]
+    JackpotBasisPointsTooHigh,
+    #[msg("Too many fee tiers")]
+    TooManyFeeTiers,
+    #[msg("Fee tiers must be sorted by pot size, each below 10000 basis points with the jackpot share")]
+    InvalidFeeTiers,
+    #[msg("Minimum fee exceeds maximum fee")]
+    InvalidFeeBounds,
+    #[msg("Referral basis points too high")]
+    ReferralBasisPointsTooHigh,
+    #[msg("Too many fee recipients")]
+    TooManyFeeRecipients,
+    #[msg("Fee recipients must be unique with shares adding up to at most 10000 basis points")]
+    InvalidFeeRecipients,
+}
+
+#[error_code]
+pub enum FfaSessionError {
+    #[msg("Cannot initialize, max entrants must be between 2 and 64")]
+    InvalidMaxEntrants,
+    #[msg("Session is full")]
+    SessionFull,
+    #[msg("Entrant is not in a paid placement")]
+    EntrantNotPlaced,
+}
+
+#[error_code]
+pub enum ReferralError {
+    #[msg("Session is not settled")]
+    SessionNotSettled,
+    #[msg("No referrals left to claim")]
+    NothingToClaim,
 }
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when a session is closed with tokens left in its vault after the termination fee
+#[event]
+pub struct DustSwept {
+    pub session: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount,
+        TokenInterface,
+        TransferChecked,
+        transfer_checked
+    }
+};
+
+use crate::errors::{GameSessionStatusError, JackpotError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AwardJackpot<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [b"jackpot", game.key().as_ref(), mint.key().as_ref()],
+        bump = jackpot.bump
+    )]
+    jackpot: Account<'info, Jackpot>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = jackpot
+    )]
+    jackpot_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> AwardJackpot<'info> {
+    /// Moves the whole jackpot into the prize pool of a session with a declared result,
+    /// so it is split between the winners like the rest of the pool. The game decides
+    /// when a result earns the jackpot.
+    pub fn award_jackpot(&mut self) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Ended &&
+            self.game_session.result.is_some(),
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            self.game_session
+                .seats()
+                .all(|(i, j)| !self.game_session.teams[i][j].recieved_rewards),
+            JackpotError::PayoutsStarted
+        );
+
+        let amount = self.jackpot_vault.amount;
+        require!(amount > 0, JackpotError::JackpotEmpty);
+
+        let signer_seeds:[&[&[u8]]; 1] = [&[
+            b"jackpot",
+            self.game.to_account_info().key.as_ref(),
+            self.mint.to_account_info().key.as_ref(),
+            &[self.jackpot.bump]
+        ]];
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.jackpot_vault.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.jackpot.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        self.game_session.record_deposit(amount)?;
+        self.game_session.jackpot_awarded = self.game_session.jackpot_awarded
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
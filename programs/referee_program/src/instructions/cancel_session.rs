@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::{GameSessionStatusError, PlayerAccountsError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CancelSession<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelSession<'info> {
+    /// Refunds every paid, unrefunded player and marks the session cancelled.
+    /// `remaining_accounts` holds the token accounts of whoever paid for those seats,
+    /// in seat order.
+    pub fn cancel_session(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            matches!(
+                self.game_session.status,
+                GameSessionStatus::Initiated | GameSessionStatus::Started | GameSessionStatus::Ended
+            ) && self.game_session.result.is_none(),
+            GameSessionStatusError::InvalidStatus
+        );
+
+        let seats: Vec<(usize, usize)> = self.game_session
+            .seats()
+            .filter(|&(i, j)| {
+                let player = self.game_session.teams[i][j];
+                player.paid && !player.refunded
+            })
+            .collect();
+
+        require!(
+            seats.len() == remaining_accounts.len(),
+            PlayerAccountsError::PlayerAccountsMismatch
+        );
+
+        for (&(i, j), account) in seats.iter().zip(remaining_accounts) {
+            let player_ata = InterfaceAccount::<TokenAccount>::try_from(account)?;
+
+            require!(
+                player_ata.owner == self.game_session.teams[i][j].payer &&
+                player_ata.mint == self.mint.key(),
+                PlayerAccountsError::InvalidPlayerAccount
+            );
+
+            let stake = self.game_session.teams[i][j].stake;
+
+            transfer_from_vault(
+                &mut self.game_session,
+                &self.vault,
+                player_ata.to_account_info(),
+                &self.mint,
+                &self.token_program,
+                stake
+            )?;
+
+            self.game_session.teams[i][j].paid = false;
+            self.game_session.teams[i][j].refunded = true;
+        }
+
+        self.game_session.status = GameSessionStatus::Cancelled;
+
+        Ok(())
+    }
+}
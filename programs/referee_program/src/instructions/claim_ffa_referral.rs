@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::ReferralError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ClaimFfaReferral<'info> {
+    #[account(mut)]
+    referrer: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = referrer
+    )]
+    referrer_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"ffa_session", game.key().as_ref(), ffa_session.seed.to_le_bytes().as_ref()],
+        bump = ffa_session.bump
+    )]
+    ffa_session: Box<Account<'info, FfaSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = ffa_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimFfaReferral<'info> {
+    /// Pays a referrer their referral on every entrant they referred, as `claim_referral`
+    /// does for team sessions.
+    pub fn claim_ffa_referral(&mut self) -> Result<()> {
+        require!(
+            self.ffa_session.status == GameSessionStatus::Ended && self.ffa_session.is_declared(),
+            ReferralError::SessionNotSettled
+        );
+
+        let entrants: Vec<usize> = self.ffa_session
+            .referred_entrants()
+            .filter(|&k| {
+                let entrant = &self.ffa_session.entrants[k];
+
+                entrant.referrer == Some(self.referrer.key()) && !entrant.referral_paid
+            })
+            .collect();
+
+        require!(!entrants.is_empty(), ReferralError::NothingToClaim);
+
+        let mut referral = 0_u64;
+
+        for k in entrants {
+            referral = referral
+                .checked_add(self.ffa_session.referral_fee(k)?)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            self.ffa_session.entrants[k].referral_paid = true;
+        }
+
+        transfer_from_vault(
+            &mut self.ffa_session,
+            &self.vault,
+            self.referrer_ata.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            referral
+        )
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::{ClaimRefundError, TransferError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ClaimFfaRefund<'info> {
+    #[account(mut)]
+    player: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player
+    )]
+    player_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"ffa_session", game.key().as_ref(), ffa_session.seed.to_le_bytes().as_ref()],
+        bump = ffa_session.bump
+    )]
+    ffa_session: Box<Account<'info, FfaSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = ffa_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimFfaRefund<'info> {
+    /// Same rules as `claim_refund`: the lobby failed to fill up before its join
+    /// deadline, or no placements were declared before its settlement deadline.
+    pub fn claim_ffa_refund(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let join_expired = self.ffa_session.status == GameSessionStatus::Initiated &&
+            now >= self.ffa_session.join_deadline &&
+            !self.ffa_session.is_fully_funded();
+
+        let settlement_expired = !self.ffa_session.is_declared() &&
+            now >= self.ffa_session.settlement_deadline;
+
+        require!(
+            join_expired || settlement_expired,
+            ClaimRefundError::RefundNotClaimable
+        );
+
+        let entrant = self.ffa_session
+            .find_entrant(self.player.key)
+            .ok_or(TransferError::PlayerNotEligibleForRefund)?;
+
+        let player = self.ffa_session.entrants[entrant];
+        require!(
+            player.paid && !player.refunded && !player.recieved_rewards,
+            TransferError::PlayerNotEligibleForRefund
+        );
+
+        transfer_from_vault(
+            &mut self.ffa_session,
+            &self.vault,
+            self.player_ata.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            player.stake
+        )?;
+
+        self.ffa_session.entrants[entrant].paid = false;
+        self.ffa_session.entrants[entrant].refunded = true;
+
+        Ok(())
+    }
+}
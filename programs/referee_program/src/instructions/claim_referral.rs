@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::ReferralError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ClaimReferral<'info> {
+    #[account(mut)]
+    referrer: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = referrer
+    )]
+    referrer_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimReferral<'info> {
+    /// Pays a referrer their referral on every seat they referred in a settled session,
+    /// so `close` never needs accounts the players chose.
+    pub fn claim_referral(&mut self) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Ended && self.game_session.result.is_some(),
+            ReferralError::SessionNotSettled
+        );
+
+        let seats: Vec<(usize, usize)> = self.game_session
+            .referred_seats()
+            .filter(|&(i, j)| {
+                let player = &self.game_session.teams[i][j];
+
+                player.referrer == Some(self.referrer.key()) && !player.referral_paid
+            })
+            .collect();
+
+        require!(!seats.is_empty(), ReferralError::NothingToClaim);
+
+        let mut referral = 0_u64;
+
+        for (i, j) in seats {
+            referral = referral
+                .checked_add(self.game_session.referral_fee(i, j)?)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            self.game_session.teams[i][j].referral_paid = true;
+        }
+
+        transfer_from_vault(
+            &mut self.game_session,
+            &self.vault,
+            self.referrer_ata.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            referral
+        )
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::{ClaimRefundError, TransferError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    /// A seated player, or the account that paid for seats in the session
+    #[account(mut)]
+    player: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player
+    )]
+    player_ata: InterfaceAccount<'info, TokenAccount>,
+    /// Token account of whoever paid for the seat, only needed when it was not the player
+    #[account(mut)]
+    payer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimRefund<'info> {
+    /// Lets a paid player take their entry back without the game signer once the
+    /// session failed to fill up before its join deadline, or no result was declared
+    /// before its settlement deadline. Whoever paid for seats can claim them back too, so
+    /// a team wallet does not depend on the players it paid for. The game's termination
+    /// fee deposit stays in the vault and is forfeited to the protocol when the session
+    /// is closed.
+    pub fn claim_refund(&mut self) -> Result<()> {
+        require!(
+            self.game_session.has_expired(Clock::get()?.unix_timestamp),
+            ClaimRefundError::RefundNotClaimable
+        );
+
+        let signer = self.player.key();
+        let seats: Vec<(usize, usize)> = self.game_session
+            .seats()
+            .filter(|&(i, j)| {
+                let player = &self.game_session.teams[i][j];
+
+                (player.player == signer || player.payer == signer) &&
+                    player.paid && !player.refunded && !player.recieved_rewards
+            })
+            .collect();
+
+        require!(!seats.is_empty(), TransferError::PlayerNotEligibleForRefund);
+
+        for (i, j) in seats {
+            let player = self.game_session.teams[i][j];
+
+            // Seats the signer paid for are refunded straight to them
+            let refund_ata = if player.payer == signer {
+                self.player_ata.to_account_info()
+            } else {
+                player.refund_account(&self.player_ata, self.payer_ata.as_ref())?.to_account_info()
+            };
+
+            transfer_from_vault(
+                &mut self.game_session,
+                &self.vault,
+                refund_ata,
+                &self.mint,
+                &self.token_program,
+                player.stake
+            )?;
+
+            self.game_session.teams[i][j].paid = false;
+            self.game_session.teams[i][j].refunded = true;
+        }
+
+        Ok(())
+    }
+}
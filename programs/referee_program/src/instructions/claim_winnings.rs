@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::{ClaimRefundError, TransferError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    #[account(mut)]
+    player: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player
+    )]
+    player_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimWinnings<'info> {
+    /// Lets a winning player take their declared winnings without the game signer once
+    /// the game failed to pay them out before the settlement deadline.
+    pub fn claim_winnings(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.game_session.settlement_deadline,
+            ClaimRefundError::WinningsNotClaimable
+        );
+
+        let (i, j) = self.game_session
+            .find_player(self.player.key)
+            .ok_or(TransferError::PlayerNotEligibleForPayout)?;
+
+        let winnings = self.game_session.winnings_due(i, j)?;
+
+        transfer_from_vault(
+            &mut self.game_session,
+            &self.vault,
+            self.player_ata.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            winnings
+        )?;
+
+        self.game_session.teams[i][j].paid = false;
+        self.game_session.teams[i][j].recieved_rewards = true;
+
+        Ok(())
+    }
+}
@@ -1,17 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{
-        Mint, TokenAccount,
-        TokenInterface,
-        TransferChecked,
-        transfer_checked,
-        CloseAccount,
-        close_account
-    }
+    token_interface::{Mint, TokenAccount, TokenInterface}
 };
 
-use crate::errors::GameSessionCloseError;
+use crate::errors::{GameSessionCloseError, JackpotError};
+use crate::events::DustSwept;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -26,7 +20,7 @@ pub struct Close<'info> {
         seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
         bump = game_session.bump
     )]
-    game_session: Account<'info, GameSession>,
+    game_session: Box<Account<'info, GameSession>>,
     #[account(
         mut,
         associated_token::mint = mint,
@@ -46,131 +40,262 @@ pub struct Close<'info> {
         associated_token::authority = program_config.admin
     )]
     protocol_ata: InterfaceAccount<'info, TokenAccount>,
+    /// Only needed when the game pre-funded the termination fee
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = game,
     )]
-    game_ata: InterfaceAccount<'info, TokenAccount>,
+    game_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = program_config.dust_recipient
+    )]
+    dust_recipient_ata: InterfaceAccount<'info, TokenAccount>,
+    /// Jackpot of the game for this mint, only needed when a settled session contributes to it
+    jackpot: Option<Account<'info, Jackpot>>,
+    #[account(mut)]
+    jackpot_vault: Option<InterfaceAccount<'info, TokenAccount>>,
     associated_token_program: Program<'info, AssociatedToken>,
     token_program: Interface<'info, TokenInterface>,
     system_program: Program<'info, System>
 }
 
 impl<'info> Close<'info> {
-    fn close_vault(&mut self) -> Result<()> {
-        let signer_seeds:[&[&[u8]]; 1] = [&[
-            b"game_session",
-            self.game.to_account_info().key.as_ref(),
-            &self.game_session.seed.to_le_bytes()[..],
-            &[self.game_session.bump]
-        ]];
+    fn close_vault(&self) -> Result<()> {
+        close_vault(&self.game_session, &self.vault, self.game.to_account_info(), &self.token_program)
+    }
+
+    fn transfer(&mut self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_from_vault(
+            &mut self.game_session,
+            &self.vault,
+            to,
+            &self.mint,
+            &self.token_program,
+            amount
+        )
+    }
+
+    /// Closes every open sponsorship to its sponsor, refunding it first unless the session
+    /// was settled and the sponsorship paid out with the prize pool. `sponsor_accounts` holds
+    /// a sponsorship, its sponsor and the sponsor's token account for each of them.
+    fn settle_sponsorships(
+        &mut self,
+        settled: bool,
+        sponsor_accounts: &'info [AccountInfo<'info>]
+    ) -> Result<()> {
+        for accounts in sponsor_accounts.chunks(3) {
+            let sponsorship = Account::<Sponsorship>::try_from(&accounts[0])?;
+            let sponsor = &accounts[1];
+
+            require!(
+                sponsorship.game_session == self.game_session.key() &&
+                sponsorship.sponsor == sponsor.key(),
+                GameSessionCloseError::SponsorAccountsMismatch
+            );
+
+            if !settled {
+                let sponsor_ata = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+
+                require!(
+                    sponsor_ata.owner == sponsorship.sponsor && sponsor_ata.mint == self.mint.key(),
+                    GameSessionCloseError::InvalidSponsorAccount
+                );
 
-        let close_accounts = CloseAccount {
-            account: self.vault.to_account_info(),
-            destination: self.game.to_account_info(),
-            authority: self.game_session.to_account_info(),
-        };
+                self.transfer(sponsor_ata.to_account_info(), sponsorship.amount)?;
 
-        let cpi_close_program = self.token_program.to_account_info();
+                self.game_session.sponsored_amount = self.game_session.sponsored_amount
+                    .checked_sub(sponsorship.amount)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+            }
+
+            sponsorship.close(sponsor.clone())?;
+        }
 
-        let cpi_close_tx = CpiContext::new_with_signer(cpi_close_program, close_accounts, &signer_seeds);
+        self.game_session.sponsorships = 0;
 
-        close_account(cpi_close_tx)?;
         Ok(())
     }
 
-    fn payout_protocol(&mut self) -> Result<()> {
-        let signer_seeds:[&[&[u8]]; 1] = [&[
-            b"game_session",
-            self.game.to_account_info().key.as_ref(),
-            &self.game_session.seed.to_le_bytes()[..],
-            &[self.game_session.bump]
-        ]];
+    /// Pays the fee recipients their share of a settled session's fee, less the referrals
+    /// set aside for its referrers, and the protocol the rest along with every referral left
+    /// unclaimed. `remaining_accounts` holds the recipients' token accounts in config order.
+    fn payout_fee(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let (referrals, unclaimed_referrals) = self.game_session.referral_fees()?;
 
-        let cpi_program = self.token_program.to_account_info();
+        let fee = self.game_session.termination_fee
+            .checked_sub(referrals)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
 
-        let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            to: self.protocol_ata.to_account_info(),
-            authority: self.game_session.to_account_info(),
-            mint: self.mint.to_account_info()
-        };
+        let (payouts, protocol_fee) = self.program_config
+            .split_fee(fee, self.mint.key(), remaining_accounts)?;
 
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
+        for (account, amount) in payouts {
+            if amount > 0 {
+                self.transfer(account, amount)?;
+            }
+        }
+
+        let protocol_fee = protocol_fee
+            .checked_add(unclaimed_referrals)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
 
-        transfer_checked(cpi_ctx, self.game_session.termination_fee, self.mint.decimals)?;
+        self.payout_protocol(protocol_fee)
+    }
+
+    /// Referrers claim their referrals from the settled session before it closes. Once the
+    /// settlement deadline passed, whatever they left unclaimed goes to the protocol.
+    fn require_referrals_claimed(&self) -> Result<()> {
+        let (_, unclaimed_referrals) = self.game_session.referral_fees()?;
+        let deadline_passed = Clock::get()?.unix_timestamp >= self.game_session.settlement_deadline;
+
+        require!(
+            unclaimed_referrals == 0 || deadline_passed,
+            GameSessionCloseError::ReferralsNotClaimed
+        );
 
         Ok(())
     }
 
+    fn payout_protocol(&mut self, amount: u64) -> Result<()> {
+        self.transfer(self.protocol_ata.to_account_info(), amount)
+    }
+
     fn payout_game(&mut self) -> Result<()> {
-        let signer_seeds:[&[&[u8]]; 1] = [&[
-            b"game_session",
-            self.game.to_account_info().key.as_ref(),
-            &self.game_session.seed.to_le_bytes()[..],
-            &[self.game_session.bump]
-        ]];
+        let game_ata = self.game_ata
+            .as_ref()
+            .ok_or(GameSessionCloseError::GameAccountRequired)?
+            .to_account_info();
+
+        let termination_fee = self.game_session.termination_fee;
+
+        self.transfer(game_ata, termination_fee)
+    }
 
-        let cpi_program = self.token_program.to_account_info();
+    fn payout_jackpot(&mut self) -> Result<()> {
+        let contribution = self.game_session.jackpot_contribution()?;
 
-        let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            to: self.game_ata.to_account_info(),
-            authority: self.game_session.to_account_info(),
-            mint: self.mint.to_account_info()
-        };
+        if contribution == 0 {
+            return Ok(());
+        }
 
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer_seeds);
+        let (jackpot, jackpot_vault) = self.jackpot
+            .as_ref()
+            .zip(self.jackpot_vault.as_ref())
+            .ok_or(JackpotError::JackpotAccountsRequired)?;
 
-        transfer_checked(cpi_ctx, self.game_session.termination_fee, self.mint.decimals)?;
+        require!(
+            jackpot.game == self.game.key() &&
+            jackpot.mint == self.mint.key() &&
+            jackpot_vault.owner == jackpot.key() &&
+            jackpot_vault.mint == self.mint.key(),
+            JackpotError::InvalidJackpotAccount
+        );
 
-        Ok(())
+        self.transfer(jackpot_vault.to_account_info(), contribution)
     }
 
-    fn transfer_termination_fee(&mut self) -> Result<()> {
-        if self.vault.amount == self.game_session.termination_fee * 2 {
-            self.payout_protocol()?;
-            self.payout_game()?;
-        } else {
-            self.payout_protocol()?;
+    /// Rounded down payouts leave dust in the vault, which is swept along with anything
+    /// transferred into the vault outside of the program so the vault can be closed.
+    fn sweep_dust(&mut self) -> Result<()> {
+        self.vault.reload()?;
+
+        let dust = self.vault.amount;
+
+        if dust == 0 {
+            return Ok(());
         }
 
+        // Tokens sent to the vault outside of the program are swept along with the dust
+        self.game_session.vault_balance = dust;
+
+        self.transfer(self.dust_recipient_ata.to_account_info(), dust)?;
+
+        emit!(DustSwept {
+            session: self.game_session.key(),
+            recipient: self.program_config.dust_recipient,
+            amount: dust,
+        });
+
         Ok(())
     }
 
-    pub fn close_game_session(&mut self) -> Result<()> {
-        let mut all_refunded = true;
-        for i in 0..(self.game_session.amount_of_teams as usize) {
-            for j in 0..(self.game_session.players_per_team as usize) {
-                if !self.game_session.teams[i][j].refunded {
-                    all_refunded = false;
-                    break;
-                }
+    /// A settled session holds the fee taken from the prize pool, which is split between the
+    /// referrers, the fee recipients and the protocol, on top of the game's termination fee
+    /// deposit if it pre-funded one, which is returned. Otherwise the deposit is forfeited
+    /// to the protocol.
+    fn transfer_termination_fee(
+        &mut self,
+        settled: bool,
+        remaining_accounts: &'info [AccountInfo<'info>]
+    ) -> Result<()> {
+        let termination_fee = self.game_session.termination_fee;
+
+        if settled {
+            self.payout_fee(remaining_accounts)?;
+
+            if self.game_session.termination_fee_paid {
+                self.payout_game()?;
             }
+        } else if self.game_session.termination_fee_paid {
+            self.payout_protocol(termination_fee)?;
         }
 
-        let mut one_team_paid = false;
-        for i in 0..(self.game_session.amount_of_teams as usize) {
-            let mut all_paid = true;
-            for j in 0..(self.game_session.players_per_team as usize) {
-                if !self.game_session.teams[i][j].recieved_rewards {
-                    all_paid = false;
-                    break;
-                }
-            }
-            if all_paid {
-                one_team_paid = true;
-                break;
-            }
+        Ok(())
+    }
+
+    pub fn close_game_session(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        // Seats that never paid hold nothing, so a session that failed to fill up can
+        // still be closed once every paid entry has been refunded.
+        let entries_returned = self.game_session
+            .seats()
+            .all(|(i, j)| !self.game_session.teams[i][j].paid);
+
+        // Seats refunded while the session was running have nothing left to be paid
+        let winners_paid = self.game_session.result.is_some() &&
+            (0..self.game_session.amount_of_teams as usize)
+                .filter(|&i| self.game_session.is_winning_team(i))
+                .all(|i| {
+                    self.game_session
+                        .team(i)
+                        .iter()
+                        .all(|player| player.recieved_rewards || player.refunded)
+                });
+
+        let settled = self.game_session.status == GameSessionStatus::Ended && winners_paid;
+
+        require!(entries_returned || settled, GameSessionCloseError::PlayersNotPaidOut);
+        require!(
+            self.vault.amount >= self.game_session.vault_balance,
+            GameSessionCloseError::VaultBalanceTooLow
+        );
+
+        if settled {
+            self.require_referrals_claimed()?;
         }
 
-        require!(all_refunded || one_team_paid, GameSessionCloseError::PlayersNotPaidOut);
+        let sponsor_accounts = 3 * self.game_session.sponsorships as usize;
 
-        self.transfer_termination_fee()?;
-        
-        self.close_vault()?;
+        require!(
+            remaining_accounts.len() >= sponsor_accounts,
+            GameSessionCloseError::SponsorAccountsMismatch
+        );
 
-        Ok(())
+        let (sponsor_accounts, remaining_accounts) = remaining_accounts.split_at(sponsor_accounts);
+
+        self.settle_sponsorships(settled, sponsor_accounts)?;
+
+        self.transfer_termination_fee(settled, remaining_accounts)?;
+
+        // Refunded sessions returned every stake, only settled ones feed the jackpot
+        if settled {
+            self.payout_jackpot()?;
+        }
+
+        self.sweep_dust()?;
+
+        self.close_vault()
     }
 }
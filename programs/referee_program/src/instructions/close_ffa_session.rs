@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::GameSessionCloseError;
+use crate::events::DustSwept;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseFfaSession<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        close = game,
+        has_one = mint,
+        seeds = [b"ffa_session", game.key().as_ref(), ffa_session.seed.to_le_bytes().as_ref()],
+        bump = ffa_session.bump
+    )]
+    ffa_session: Box<Account<'info, FfaSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = ffa_session,
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"program_config", game.key().as_ref()],
+        bump = program_config.bump,
+        has_one = protocol_ata
+    )]
+    program_config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = program_config.admin
+    )]
+    protocol_ata: InterfaceAccount<'info, TokenAccount>,
+    /// Only needed when the game pre-funded the termination fee
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game,
+    )]
+    game_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = program_config.dust_recipient
+    )]
+    dust_recipient_ata: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>
+}
+
+impl<'info> CloseFfaSession<'info> {
+    fn transfer(&mut self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_from_vault(
+            &mut self.ffa_session,
+            &self.vault,
+            to,
+            &self.mint,
+            &self.token_program,
+            amount
+        )
+    }
+
+    fn close_vault(&self) -> Result<()> {
+        close_vault(&self.ffa_session, &self.vault, self.game.to_account_info(), &self.token_program)
+    }
+
+    /// Pays the fee recipients their share of a settled session's fee, less the referrals
+    /// set aside for its referrers, and the protocol the rest along with every referral left
+    /// unclaimed. `remaining_accounts` holds the recipients' token accounts in config order.
+    fn payout_fee(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let (referrals, unclaimed_referrals) = self.ffa_session.referral_fees()?;
+
+        let fee = self.ffa_session.termination_fee
+            .checked_sub(referrals)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let (payouts, protocol_fee) = self.program_config
+            .split_fee(fee, self.mint.key(), remaining_accounts)?;
+
+        for (account, amount) in payouts {
+            if amount > 0 {
+                self.transfer(account, amount)?;
+            }
+        }
+
+        let protocol_fee = protocol_fee
+            .checked_add(unclaimed_referrals)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.payout_protocol(protocol_fee)
+    }
+
+    /// Referrers claim their referrals from the settled session before it closes. Once the
+    /// settlement deadline passed, whatever they left unclaimed goes to the protocol.
+    fn require_referrals_claimed(&self) -> Result<()> {
+        let (_, unclaimed_referrals) = self.ffa_session.referral_fees()?;
+        let deadline_passed = Clock::get()?.unix_timestamp >= self.ffa_session.settlement_deadline;
+
+        require!(
+            unclaimed_referrals == 0 || deadline_passed,
+            GameSessionCloseError::ReferralsNotClaimed
+        );
+
+        Ok(())
+    }
+
+    fn payout_protocol(&mut self, amount: u64) -> Result<()> {
+        self.transfer(self.protocol_ata.to_account_info(), amount)
+    }
+
+    fn payout_game(&mut self) -> Result<()> {
+        let game_ata = self.game_ata
+            .as_ref()
+            .ok_or(GameSessionCloseError::GameAccountRequired)?
+            .to_account_info();
+
+        let termination_fee = self.ffa_session.termination_fee;
+
+        self.transfer(game_ata, termination_fee)
+    }
+
+    /// A settled session's fee is split as in `close` for team sessions and a pre-funded
+    /// deposit is returned to the game, otherwise the deposit is forfeited to the protocol.
+    fn transfer_termination_fee(
+        &mut self,
+        settled: bool,
+        remaining_accounts: &'info [AccountInfo<'info>]
+    ) -> Result<()> {
+        let termination_fee = self.ffa_session.termination_fee;
+
+        if settled {
+            self.payout_fee(remaining_accounts)?;
+
+            if self.ffa_session.termination_fee_paid {
+                self.payout_game()?;
+            }
+        } else if self.ffa_session.termination_fee_paid {
+            self.payout_protocol(termination_fee)?;
+        }
+
+        Ok(())
+    }
+
+    /// Settles the termination fee the same way `close` does for team sessions, including
+    /// the fee recipients' token accounts in `remaining_accounts`. Nothing is paid into the
+    /// jackpot, which only team sessions feed.
+    pub fn close_ffa_session(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let entries_returned = self.ffa_session.entrants.iter().all(|entrant| !entrant.paid);
+
+        let winners_paid = self.ffa_session.is_declared() &&
+            self.ffa_session.placements
+                .iter()
+                .map(|&entrant| &self.ffa_session.entrants[entrant as usize])
+                .all(|entrant| entrant.recieved_rewards || entrant.refunded);
+
+        let settled = self.ffa_session.status == GameSessionStatus::Ended && winners_paid;
+
+        require!(entries_returned || settled, GameSessionCloseError::PlayersNotPaidOut);
+        require!(
+            self.vault.amount >= self.ffa_session.vault_balance,
+            GameSessionCloseError::VaultBalanceTooLow
+        );
+
+        if settled {
+            self.require_referrals_claimed()?;
+        }
+
+        self.transfer_termination_fee(settled, remaining_accounts)?;
+
+        // Anything left is rounding dust or tokens sent to the vault outside of the program
+        self.vault.reload()?;
+
+        let dust = self.vault.amount;
+
+        if dust > 0 {
+            self.ffa_session.vault_balance = dust;
+
+            self.transfer(self.dust_recipient_ata.to_account_info(), dust)?;
+
+            emit!(DustSwept {
+                session: self.ffa_session.key(),
+                recipient: self.program_config.dust_recipient,
+                amount: dust,
+            });
+        }
+
+        self.close_vault()
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::{DeclareResultError, GameSessionStatusError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct DeclareResult<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+}
+
+impl<'info> DeclareResult<'info> {
+    pub fn declare_result(&mut self, result: GameSessionResult) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Ended,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            self.game_session.result.is_none(),
+            DeclareResultError::ResultAlreadyDeclared
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.game_session.settlement_deadline,
+            DeclareResultError::SettlementDeadlinePassed
+        );
+        match result {
+            GameSessionResult::Winner { team } => {
+                require!(
+                    team < self.game_session.amount_of_teams,
+                    DeclareResultError::InvalidWinningTeam
+                );
+                require!(
+                    self.game_session.payout_table[0] == 10_000,
+                    DeclareResultError::PlacementsRequired
+                );
+            },
+            GameSessionResult::Ranked { placements } => {
+                let amount_of_teams = self.game_session.amount_of_teams as usize;
+
+                // Every team has to show up exactly once in the first `amount_of_teams` places
+                let mut ranked: u16 = 0;
+                for &team in &placements[..amount_of_teams] {
+                    require!(
+                        (team as usize) < amount_of_teams && ranked & (1 << team) == 0,
+                        DeclareResultError::InvalidPlacements
+                    );
+                    ranked |= 1 << team;
+                }
+            },
+            GameSessionResult::Draw => {},
+        }
+
+        self.game_session.result = Some(result);
+
+        Ok(())
+    }
+}
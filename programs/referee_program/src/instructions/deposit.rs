@@ -9,7 +9,7 @@ use anchor_spl::{
     }
 };
 
-use crate::{errors::DepositError, state::*};
+use crate::{errors::{DepositError, GameSessionStatusError}, state::*};
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
@@ -25,10 +25,11 @@ pub struct Deposit<'info> {
     player_ata: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
+        has_one = mint,
         seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
         bump = game_session.bump,
     )]
-    game_session: Account<'info, GameSession>,
+    game_session: Box<Account<'info, GameSession>>,
     #[account(
         mut,
         associated_token::mint = mint,
@@ -40,49 +41,120 @@ pub struct Deposit<'info> {
 }
 
 impl<'info> Deposit<'info> {
-    pub fn deposit_entry_fee(&mut self) -> Result<()> {
-        let mut updated_teams = [[Player {
-            player: Pubkey::default(),
-            paid: false,
+    fn require_joinable(&self) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Initiated,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.game_session.join_deadline,
+            DepositError::JoinDeadlinePassed
+        );
+
+        Ok(())
+    }
+
+    fn require_valid_referrer(&self, referrer: Option<Pubkey>) -> Result<()> {
+        require!(
+            referrer != Some(self.player.key()),
+            DepositError::InvalidReferrer
+        );
+
+        Ok(())
+    }
+
+    fn transfer_entry_fee(&mut self, amount: u64) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.player_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.player.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        self.game_session.record_deposit(amount)
+    }
+
+    pub fn deposit_entry_fee(&mut self, stake: Option<u64>, referrer: Option<Pubkey>) -> Result<()> {
+        self.require_joinable()?;
+        self.require_valid_referrer(referrer)?;
+
+        let (i, j) = self.game_session
+            .find_player(self.player.key)
+            .ok_or(DepositError::PlayerNotInSession)?;
+
+        require!(!self.game_session.teams[i][j].paid, DepositError::PlayerAlreadyPaid);
+
+        let stake = self.game_session.entry_stake(i, stake)?;
+
+        self.transfer_entry_fee(stake)?;
+
+        self.game_session.teams[i][j] = Player {
+            player: self.player.key(),
+            payer: self.player.key(),
+            stake,
+            paid: true,
+            refunded: false,
+            recieved_rewards: false,
+            referrer,
+            referral_paid: false
+        };
+
+        Ok(())
+    }
+
+    /// Seats the signer in an open seat and takes their entry fee. Sessions created with
+    /// `game_assigns_seats` also require the game to co-sign the seat assignment.
+    pub fn deposit_into_open_seat(
+        &mut self,
+        team_index: u8,
+        seat_index: u8,
+        stake: Option<u64>,
+        referrer: Option<Pubkey>
+    ) -> Result<()> {
+        self.require_joinable()?;
+        self.require_valid_referrer(referrer)?;
+
+        require!(
+            team_index < self.game_session.amount_of_teams &&
+            seat_index < self.game_session.players_per_team[team_index as usize],
+            DepositError::InvalidSeat
+        );
+        require!(
+            !self.game_session.game_assigns_seats || self.game.to_account_info().is_signer,
+            DepositError::GameSignatureRequired
+        );
+        require!(
+            self.game_session.find_player(self.player.key).is_none(),
+            DepositError::PlayerAlreadySeated
+        );
+
+        let (i, j) = (team_index as usize, seat_index as usize);
+
+        require!(
+            self.game_session.teams[i][j].player == Pubkey::default(),
+            DepositError::SeatNotOpen
+        );
+
+        let stake = self.game_session.entry_stake(i, stake)?;
+
+        self.transfer_entry_fee(stake)?;
+
+        self.game_session.teams[i][j] = Player {
+            player: self.player.key(),
+            payer: self.player.key(),
+            stake,
+            paid: true,
             refunded: false,
-            recieved_rewards: false
-        }; MAX_PLAYERS_PER_TEAM]; MAX_TEAMS_LENGTH];
-
-        for i in 0..(self.game_session.amount_of_teams as usize) {
-            for j in 0..(self.game_session.players_per_team as usize) {
-                if self.game_session.teams[i][j].player == *self.player.to_account_info().key {
-                    require!(!self.game_session.teams[i][j].paid, DepositError::PlayerAlreadyPaid);
-                    
-                    let cpi_program = self.token_program.to_account_info();
-
-                    let cpi_accounts = TransferChecked {
-                        from: self.player_ata.to_account_info(),
-                        to: self.vault.to_account_info(),
-                        authority: self.player.to_account_info(),
-                        mint: self.mint.to_account_info(),
-                    };
-            
-                    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            
-                    transfer_checked(cpi_ctx, self.game_session.session_entry_cost_per_player, self.mint.decimals)?;
-                    updated_teams[i][j] = Player {
-                            player: self.game_session.teams[i][j].player,
-                            paid: true,
-                            refunded: false,
-                            recieved_rewards: false
-                        };
-                } else {
-                    updated_teams[i][j] = Player {
-                            player: self.game_session.teams[i][j].player,
-                            paid: self.game_session.teams[i][j].paid,
-                            refunded: self.game_session.teams[i][j].refunded,
-                            recieved_rewards: self.game_session.teams[i][j].recieved_rewards
-                        };
-                }
-            }
-        }
-
-        self.game_session.teams = updated_teams;
+            recieved_rewards: false,
+            referrer,
+            referral_paid: false
+        };
 
         Ok(())
     }
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount,
+        TokenInterface,
+        TransferChecked,
+        transfer_checked
+    }
+};
+
+use crate::{errors::{DepositError, GameSessionStatusError}, state::*};
+
+#[derive(Accounts)]
+pub struct DepositFor<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    player: SystemAccount<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payer
+    )]
+    payer_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump,
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session,
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DepositFor<'info> {
+    /// Pays the entry of a seated player from another account, e.g. a friend, a guild or
+    /// a promotions wallet. The seat's refunds go back to the payer, its winnings to the player.
+    pub fn deposit_for(&mut self, stake: Option<u64>) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Initiated,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.game_session.join_deadline,
+            DepositError::JoinDeadlinePassed
+        );
+
+        let (i, j) = self.game_session
+            .find_player(self.player.key)
+            .ok_or(DepositError::PlayerNotInSession)?;
+
+        require!(!self.game_session.teams[i][j].paid, DepositError::PlayerAlreadyPaid);
+
+        let stake = self.game_session.entry_stake(i, stake)?;
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.payer_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.payer.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, stake, self.mint.decimals)?;
+
+        self.game_session.record_deposit(stake)?;
+
+        self.game_session.teams[i][j] = Player {
+            player: self.player.key(),
+            payer: self.payer.key(),
+            stake,
+            paid: true,
+            refunded: false,
+            recieved_rewards: false,
+            referrer: None,
+            referral_paid: false
+        };
+
+        Ok(())
+    }
+}
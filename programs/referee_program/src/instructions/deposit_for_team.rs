@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount,
+        TokenInterface,
+        TransferChecked,
+        transfer_checked
+    }
+};
+
+use crate::{errors::{DepositError, GameSessionStatusError}, state::*};
+
+#[derive(Accounts)]
+pub struct DepositForTeam<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payer
+    )]
+    payer_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump,
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session,
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DepositForTeam<'info> {
+    /// Pays the team's whole entry cost from one account, e.g. a clan's team wallet, and
+    /// marks every seat of the team as paid. Refunds of those seats go back to the payer.
+    pub fn deposit_for_team(&mut self, team_index: u8) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Initiated,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.game_session.join_deadline,
+            DepositError::JoinDeadlinePassed
+        );
+        require!(
+            team_index < self.game_session.amount_of_teams,
+            DepositError::InvalidSeat
+        );
+
+        let i = team_index as usize;
+
+        require!(
+            self.game_session
+                .team(i)
+                .iter()
+                .all(|player| player.player != Pubkey::default() && !player.paid),
+            DepositError::TeamNotPayable
+        );
+
+        let stake = self.game_session.session_entry_cost_per_player[i];
+        let amount = stake
+            .checked_mul(self.game_session.players_per_team[i] as u64)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.payer_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.payer.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        self.game_session.record_deposit(amount)?;
+
+        let players = self.game_session.players_per_team[i] as usize;
+
+        for seat in self.game_session.teams[i][..players].iter_mut() {
+            seat.payer = self.payer.key();
+            seat.stake = stake;
+            seat.paid = true;
+            seat.refunded = false;
+            seat.referrer = None;
+            seat.referral_paid = false;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::{ForfeitError, GameSessionStatusError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct DesignateCaptain<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+}
+
+impl<'info> DesignateCaptain<'info> {
+    pub fn designate_captain(&mut self, team_index: u8, captain: Pubkey) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Initiated ||
+            self.game_session.status == GameSessionStatus::Started,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            team_index < self.game_session.amount_of_teams,
+            ForfeitError::InvalidTeam
+        );
+        require!(
+            captain != Pubkey::default() &&
+            self.game_session.find_player(&captain).map(|(i, _)| i) == Some(team_index as usize),
+            ForfeitError::CaptainNotInTeam
+        );
+
+        self.game_session.captains[team_index as usize] = captain;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::{DeclareResultError, ForfeitError, GameSessionStatusError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct Forfeit<'info> {
+    #[account(mut)]
+    player: Signer<'info>,
+    game: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+}
+
+impl<'info> Forfeit<'info> {
+    /// Concedes the match for a team. The team's captain concedes right away, otherwise
+    /// every member has to sign a forfeit before the other team is ranked first.
+    pub fn forfeit(&mut self, team_index: u8) -> Result<()> {
+        require!(
+            (self.game_session.status == GameSessionStatus::Started ||
+            self.game_session.status == GameSessionStatus::Ended) &&
+            self.game_session.result.is_none(),
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            self.game_session.amount_of_teams == 2,
+            ForfeitError::ForfeitRequiresTwoTeams
+        );
+        require!(
+            team_index < self.game_session.amount_of_teams,
+            ForfeitError::InvalidTeam
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.game_session.settlement_deadline,
+            DeclareResultError::SettlementDeadlinePassed
+        );
+
+        let i = team_index as usize;
+        let team = self.game_session.teams[i];
+        let is_staked = |j: usize| team[j].paid && !team[j].refunded;
+
+        // Only seats that still have a stake in the match get a say, so a refunded player
+        // can neither vote nor concede as captain
+        let seat = self.game_session
+            .find_player(self.player.key)
+            .filter(|&(team, j)| team == i && is_staked(j));
+
+        // The captain only speaks for the team while still seated in it
+        let is_captain = seat.is_some() &&
+            self.game_session.captains[i] == self.player.key();
+
+        require!(is_captain || seat.is_some(), ForfeitError::NotTeamMember);
+
+        if let Some((_, j)) = seat {
+            self.game_session.forfeit_votes[i] |= 1 << j;
+        }
+
+        let all_members_voted = (0..self.game_session.players_per_team[i] as usize)
+            .filter(|&j| is_staked(j))
+            .all(|j| self.game_session.forfeit_votes[i] & (1 << j) != 0);
+
+        if is_captain || all_members_voted {
+            let mut placements = [0; MAX_TEAMS_LENGTH];
+            placements[0] = 1 - team_index;
+            placements[1] = team_index;
+
+            self.game_session.result = Some(GameSessionResult::Ranked { placements });
+            self.game_session.status = GameSessionStatus::Ended;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        get_mint_extension_data,
+        spl_token_2022::extension::transfer_fee::TransferFeeConfig,
+        transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked
+    }
+};
+
+use crate::errors::{FfaSessionError, GameSessionInitializeError};
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct InitializeFfaSession<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    /// Only needed when the game pre-funds the termination fee
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game
+    )]
+    game_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = game,
+        space = 8 + FfaSession::INIT_SPACE,
+        seeds = [b"ffa_session", game.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    ffa_session: Box<Account<'info, FfaSession>>,
+    #[account(
+        init,
+        payer = game,
+        associated_token::mint = mint,
+        associated_token::authority = ffa_session
+    )]
+    vault: InterfaceAccount <'info, TokenAccount>,
+    #[account(
+        seeds = [b"program_config", game.key().as_ref()],
+        bump = program_config.bump,
+    )]
+    program_config: Account<'info, ProgramConfig>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>
+}
+
+impl<'info> InitializeFfaSession<'info> {
+    /// Opens a solo lobby that entrants join by depositing. The prize pool is split
+    /// across the top placements according to `payout_table`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_ffa_session(
+        &mut self,
+        seed: u64,
+        entry_cost_per_player: u64,
+        max_entrants: u8,
+        join_deadline: i64,
+        settlement_deadline: i64,
+        payout_table: Vec<u16>,
+        bumps: &InitializeFfaSessionBumps
+    ) -> Result<()> {
+        // Transfer fees would leave the vault holding less than the stakes it records
+        require!(
+            get_mint_extension_data::<TransferFeeConfig>(&self.mint.to_account_info()).is_err(),
+            GameSessionInitializeError::TransferFeeMintNotSupported
+        );
+        require!(
+            (2..=MAX_FFA_ENTRANTS).contains(&(max_entrants as usize)),
+            FfaSessionError::InvalidMaxEntrants
+        );
+        require!(
+            join_deadline > Clock::get()?.unix_timestamp,
+            GameSessionInitializeError::JoinDeadlineInPast
+        );
+        require!(
+            settlement_deadline > join_deadline,
+            GameSessionInitializeError::SettlementDeadlineBeforeJoinDeadline
+        );
+        require!(
+            !payout_table.is_empty() &&
+            payout_table.len() <= max_entrants as usize &&
+            payout_table.iter().map(|&share| share as u32).sum::<u32>() == 10_000,
+            GameSessionInitializeError::InvalidPayoutTable
+        );
+
+        // Entry costs are in base units of the mint
+        let pot = entry_cost_per_player
+            .checked_mul(max_entrants as u64)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let termination_fee = self.program_config.termination_fee(pot)?;
+
+        require!(
+            termination_fee > 0 || self.program_config.fee_basis_points_for(pot) == 0,
+            GameSessionInitializeError::TerminationFeeRoundsToZero
+        );
+        require!(termination_fee <= pot, GameSessionInitializeError::TerminationFeeExceedsPot);
+
+        self.ffa_session.set_inner(FfaSession {
+            seed,
+            game: self.game.key(),
+            mint: self.mint.key(),
+            entry_cost_per_player,
+            max_entrants,
+            entrants: Vec::new(),
+            payout_table,
+            placements: Vec::new(),
+            termination_fee,
+            termination_fee_paid: false,
+            referral_basis_points: self.program_config.referral_basis_points,
+            vault_balance: 0,
+            status: GameSessionStatus::Initiated,
+            join_deadline,
+            settlement_deadline,
+            bump: bumps.ffa_session,
+        });
+
+        Ok(())
+    }
+
+    /// Pre-funds the termination fee the same way team sessions do.
+    pub fn deposit_termination_fee(&mut self) -> Result<()> {
+        if self.program_config.fee_from_pot {
+            return Ok(());
+        }
+
+        let game_ata = self.game_ata
+            .as_ref()
+            .ok_or(GameSessionInitializeError::GameAccountRequired)?;
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: game_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.game.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, self.ffa_session.termination_fee, self.mint.decimals)?;
+
+        let termination_fee = self.ffa_session.termination_fee;
+        self.ffa_session.record_deposit(termination_fee)?;
+        self.ffa_session.termination_fee_paid = true;
+
+        Ok(())
+    }
+}
@@ -3,6 +3,8 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{
+        get_mint_extension_data,
+        spl_token_2022::extension::transfer_fee::TransferFeeConfig,
         transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked
     }
 };
@@ -16,12 +18,13 @@ pub struct InitializeGameSession<'info> {
     #[account(mut)]
     game: Signer<'info>,
     mint: InterfaceAccount<'info, Mint>,
+    /// Only needed when the game pre-funds the termination fee
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = game
     )]
-    game_ata: InterfaceAccount<'info, TokenAccount>,
+    game_ata: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init,
         payer = game,
@@ -30,7 +33,7 @@ pub struct InitializeGameSession<'info> {
         seeds = [b"game_session", game.key().as_ref(), seed.to_le_bytes().as_ref()],
         bump,
     )]
-    game_session: Account<'info, GameSession>,
+    game_session: Box<Account<'info, GameSession>>,
     #[account(
         init,
         payer = game,
@@ -50,13 +53,25 @@ pub struct InitializeGameSession<'info> {
 }
 
 impl<'info> InitializeGameSession<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_game_session(
         &mut self,
         seed: u64,
         session_entry_cost_per_team: u64,
         teams: Vec<Vec<Pubkey>>,
+        join_deadline: i64,
+        settlement_deadline: i64,
+        game_assigns_seats: bool,
+        payout_table: Vec<u16>,
+        allow_unequal_teams: bool,
+        variable_stakes: bool,
         bumps: &InitializeGameSessionBumps
     ) -> Result<()> {
+        // Transfer fees would leave the vault holding less than the stakes it records
+        require!(
+            get_mint_extension_data::<TransferFeeConfig>(&self.mint.to_account_info()).is_err(),
+            GameSessionInitializeError::TransferFeeMintNotSupported
+        );
         require!(
             teams.len() <= MAX_TEAMS_LENGTH,
             GameSessionInitializeError::TeamsToMany
@@ -66,72 +81,122 @@ impl<'info> InitializeGameSession<'info> {
             GameSessionInitializeError::TeamToBig
         );
         require!(
-            teams.iter().all(|team| team.len() == teams[0].len()),
+            allow_unequal_teams || teams.iter().all(|team| team.len() == teams[0].len()),
             GameSessionInitializeError::TeamsNotSameLength
         );
         require!(
-            teams.iter().all(|team| team.len() != 0),
+            teams.iter().all(|team| !team.is_empty()),
             GameSessionInitializeError::TeamNoPlayers
         );
+        require!(
+            join_deadline > Clock::get()?.unix_timestamp,
+            GameSessionInitializeError::JoinDeadlineInPast
+        );
+        require!(
+            settlement_deadline > join_deadline,
+            GameSessionInitializeError::SettlementDeadlineBeforeJoinDeadline
+        );
+        require!(
+            !payout_table.is_empty() &&
+            payout_table.len() <= teams.len() &&
+            payout_table.iter().map(|&share| share as u32).sum::<u32>() == 10_000,
+            GameSessionInitializeError::InvalidPayoutTable
+        );
+
+        // Open seats are left as the default pubkey and claimed on deposit
+        let seated = || teams.iter().flatten().filter(|player| **player != Pubkey::default());
 
-        let set: HashSet<Pubkey> = teams.iter().flatten().cloned().collect();
+        let set: HashSet<Pubkey> = seated().cloned().collect();
 
         require!(
-            set.len() == teams.iter().flatten().count(),
+            set.len() == seated().count(),
             GameSessionInitializeError::PlayersNotUnique
         );
 
-        let mut teams_array: [[Player; MAX_PLAYERS_PER_TEAM]; MAX_TEAMS_LENGTH] = [[
-            Player {
-                player: Pubkey::default(),
-                paid: false,
-                refunded: false,
-                recieved_rewards: false
-            }
-        ; MAX_PLAYERS_PER_TEAM]; MAX_TEAMS_LENGTH];
-
-        for (i, team) in teams.iter().enumerate() {
-            for (j, player) in team.iter().enumerate() {
-                teams_array[i][j].player = *player;
-            }
-        }
-
-        let termination_fee = session_entry_cost_per_team
-            .checked_mul(10_u64.pow(self.mint.decimals as u32 - 4)) // - 4 for basis points adjustement
-            .ok_or(ProgramError::ArithmeticOverflow)?
+        // Entry costs are in base units of the mint
+        let pot = session_entry_cost_per_team
             .checked_mul(teams.len() as u64)
-            .ok_or(ProgramError::ArithmeticOverflow)?
-            .checked_mul(self.program_config.fee_basis_points)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
-        let session_entry_cost_per_player = session_entry_cost_per_team
-            .checked_mul(10_u64.pow(self.mint.decimals as u32))
-            .ok_or(ProgramError::ArithmeticOverflow)?
-            .checked_div(teams[0].len() as u64)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let termination_fee = self.program_config.termination_fee(pot)?;
 
-        let entry_cost_per_team = session_entry_cost_per_team
-            .checked_mul(10_u64.pow(self.mint.decimals as u32))
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+        require!(
+            termination_fee > 0 || self.program_config.fee_basis_points_for(pot) == 0,
+            GameSessionInitializeError::TerminationFeeRoundsToZero
+        );
+        // A minimum fee must still leave the stakes enough to feed the jackpot
+        require!(
+            (termination_fee as u128) * 10_000 +
+            (pot as u128) * (self.program_config.jackpot_basis_points as u128) <=
+            (pot as u128) * 10_000,
+            GameSessionInitializeError::TerminationFeeExceedsPot
+        );
+
+        // Every team stakes the same amount, smaller teams pay more per player
+        let mut players_per_team = [0; MAX_TEAMS_LENGTH];
+        let mut session_entry_cost_per_player = [0; MAX_TEAMS_LENGTH];
+        for (i, team) in teams.iter().enumerate() {
+            require!(
+                session_entry_cost_per_team.checked_rem(team.len() as u64) == Some(0),
+                GameSessionInitializeError::EntryCostNotDivisible
+            );
+
+            players_per_team[i] = team.len() as u8;
+            session_entry_cost_per_player[i] = session_entry_cost_per_team / team.len() as u64;
+        }
 
         self.game_session.set_inner(GameSession {
             seed,
             game: self.game.key(),
             mint: self.mint.key(),
-            session_entry_cost_per_team: entry_cost_per_team,
+            session_entry_cost_per_team,
             session_entry_cost_per_player,
             amount_of_teams: teams.len() as u8,
-            players_per_team: teams[0].len() as u8,
-            teams: teams_array,
+            players_per_team,
+            teams: [[Player::default(); MAX_PLAYERS_PER_TEAM]; MAX_TEAMS_LENGTH],
+            game_assigns_seats,
+            variable_stakes,
+            payout_table: [0; MAX_TEAMS_LENGTH],
+            captains: [Pubkey::default(); MAX_TEAMS_LENGTH],
+            forfeit_votes: [0; MAX_TEAMS_LENGTH],
             termination_fee,
             termination_fee_paid: false,
+            sponsored_amount: 0,
+            sponsorships: 0,
+            jackpot_basis_points: self.program_config.jackpot_basis_points,
+            referral_basis_points: self.program_config.referral_basis_points,
+            jackpot_awarded: 0,
+            vault_balance: 0,
+            status: GameSessionStatus::Initiated,
+            result: None,
+            join_deadline,
+            settlement_deadline,
             bump: bumps.game_session,
         });
 
+        // Filled in place, the session is too large to build a second copy on the stack
+        for (i, team) in teams.iter().enumerate() {
+            for (j, player) in team.iter().enumerate() {
+                self.game_session.teams[i][j].player = *player;
+            }
+        }
+
+        self.game_session.payout_table[..payout_table.len()].copy_from_slice(&payout_table);
+
         Ok(())
     }
 
+    /// Pre-funds the termination fee from the game's token account as collateral, unless
+    /// the config takes the fee from the prize pool alone.
     pub fn deposit_termination_fee(&mut self) -> Result<()> {
+        if self.program_config.fee_from_pot {
+            return Ok(());
+        }
+
+        let game_ata = self.game_ata
+            .as_ref()
+            .ok_or(GameSessionInitializeError::GameAccountRequired)?;
+
         let signer_seeds:[&[&[u8]]; 1] = [&[
             b"game_session",
             self.game.to_account_info().key.as_ref(),
@@ -142,7 +207,7 @@ impl<'info> InitializeGameSession<'info> {
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked {
-            from: self.game_ata.to_account_info(),
+            from: game_ata.to_account_info(),
             to: self.vault.to_account_info(),
             authority: self.game.to_account_info(),
             mint: self.mint.to_account_info(),
@@ -152,6 +217,8 @@ impl<'info> InitializeGameSession<'info> {
 
         transfer_checked(cpi_ctx, self.game_session.termination_fee, self.mint.decimals)?;
 
+        let termination_fee = self.game_session.termination_fee;
+        self.game_session.record_deposit(termination_fee)?;
         self.game_session.termination_fee_paid = true;
 
         Ok(())
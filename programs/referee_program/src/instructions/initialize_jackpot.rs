@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount,
+        TokenInterface
+    }
+};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeJackpot<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = game,
+        space = 8 + Jackpot::INIT_SPACE,
+        seeds = [b"jackpot", game.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    jackpot: Account<'info, Jackpot>,
+    #[account(
+        init,
+        payer = game,
+        associated_token::mint = mint,
+        associated_token::authority = jackpot
+    )]
+    jackpot_vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>
+}
+
+impl<'info> InitializeJackpot<'info> {
+    pub fn initialize_jackpot(&mut self, bumps: &InitializeJackpotBumps) -> Result<()> {
+        self.jackpot.set_inner(Jackpot {
+            game: self.game.key(),
+            mint: self.mint.key(),
+            bump: bumps.jackpot
+        });
+
+        Ok(())
+    }
+}
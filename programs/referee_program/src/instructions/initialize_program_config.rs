@@ -43,14 +43,28 @@ impl<'info> InitializeProgramConfig<'info> {
     pub fn initialize_program_config(
         &mut self,
         fee_basis_points: u64,
+        dust_recipient: Pubkey,
+        jackpot_basis_points: u64,
         bumps: &InitializeProgramConfigBumps
     ) -> Result<()> {
         require!(fee_basis_points < 10000, ProgramConfigCode::FeeBasisPointsTooHigh);
+        require!(
+            fee_basis_points.checked_add(jackpot_basis_points).is_some_and(|total| total < 10000),
+            ProgramConfigCode::JackpotBasisPointsTooHigh
+        );
 
         self.program_config.set_inner(ProgramConfig {
             admin: self.authority.key(),
             protocol_ata: self.protocol_ata.key(),
+            dust_recipient,
             fee_basis_points,
+            min_fee: 0,
+            max_fee: 0,
+            fee_tiers: Vec::new(),
+            jackpot_basis_points,
+            fee_recipients: Vec::new(),
+            referral_basis_points: 0,
+            fee_from_pot: false,
             bump: bumps.program_config
         });
 
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount,
+        TokenInterface,
+        TransferChecked,
+        transfer_checked
+    }
+};
+
+use crate::errors::{DepositError, FfaSessionError, GameSessionStatusError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct JoinFfaSession<'info> {
+    #[account(mut)]
+    player: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player
+    )]
+    player_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"ffa_session", game.key().as_ref(), ffa_session.seed.to_le_bytes().as_ref()],
+        bump = ffa_session.bump,
+    )]
+    ffa_session: Box<Account<'info, FfaSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = ffa_session,
+    )]
+    vault: InterfaceAccount <'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> JoinFfaSession<'info> {
+    pub fn join_ffa_session(&mut self, referrer: Option<Pubkey>) -> Result<()> {
+        require!(
+            self.ffa_session.status == GameSessionStatus::Initiated,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.ffa_session.join_deadline,
+            DepositError::JoinDeadlinePassed
+        );
+        require!(
+            self.ffa_session.entrants.len() < self.ffa_session.max_entrants as usize,
+            FfaSessionError::SessionFull
+        );
+        require!(
+            self.ffa_session.find_entrant(self.player.key).is_none(),
+            DepositError::PlayerAlreadySeated
+        );
+        require!(
+            referrer != Some(self.player.key()),
+            DepositError::InvalidReferrer
+        );
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.player_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.player.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        let stake = self.ffa_session.entry_cost_per_player;
+
+        transfer_checked(cpi_ctx, stake, self.mint.decimals)?;
+
+        self.ffa_session.record_deposit(stake)?;
+
+        self.ffa_session.entrants.push(Player {
+            player: self.player.key(),
+            payer: self.player.key(),
+            stake,
+            paid: true,
+            refunded: false,
+            recieved_rewards: false,
+            referrer,
+            referral_paid: false
+        });
+
+        Ok(())
+    }
+}
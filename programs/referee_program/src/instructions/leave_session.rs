@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::{DepositError, GameSessionStatusError, TransferError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct LeaveSession<'info> {
+    #[account(mut)]
+    player: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player
+    )]
+    player_ata: InterfaceAccount<'info, TokenAccount>,
+    /// Token account of whoever paid for the seat, only needed when it was not the player
+    #[account(mut)]
+    payer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> LeaveSession<'info> {
+    /// Returns a paid player's entry to whoever paid it before the match starts and
+    /// frees their seat, so it can be claimed again through `join_open_seat`.
+    pub fn leave_session(&mut self) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Initiated,
+            GameSessionStatusError::InvalidStatus
+        );
+
+        let (i, j) = self.game_session
+            .find_player(self.player.key)
+            .ok_or(DepositError::PlayerNotInSession)?;
+
+        let player = self.game_session.teams[i][j];
+        require!(
+            player.paid && !player.refunded && !player.recieved_rewards,
+            TransferError::PlayerNotEligibleForRefund
+        );
+
+        let refund_ata = player.refund_account(&self.player_ata, self.payer_ata.as_ref())?;
+
+        transfer_from_vault(
+            &mut self.game_session,
+            &self.vault,
+            refund_ata.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            player.stake
+        )?;
+
+        self.game_session.teams[i][j] = Player {
+            player: Pubkey::default(),
+            payer: Pubkey::default(),
+            stake: 0,
+            paid: false,
+            refunded: false,
+            recieved_rewards: false,
+            referrer: None,
+            referral_paid: false
+        };
+
+        // A captain who gives up their seat can no longer concede for the team
+        if self.game_session.captains[i] == player.player {
+            self.game_session.captains[i] = Pubkey::default();
+        }
+
+        Ok(())
+    }
+}
@@ -15,3 +15,87 @@ pub mod update_program_config;
 
 pub use initialize_program_config::*;
 pub mod initialize_program_config;
+
+pub use update_session_status::*;
+pub mod update_session_status;
+
+pub use declare_result::*;
+pub mod declare_result;
+
+pub use claim_refund::*;
+pub mod claim_refund;
+
+pub use claim_winnings::*;
+pub mod claim_winnings;
+
+pub use cancel_session::*;
+pub mod cancel_session;
+
+pub use payout_team::*;
+pub mod payout_team;
+
+pub use leave_session::*;
+pub mod leave_session;
+
+pub use replace_player::*;
+pub mod replace_player;
+
+pub use designate_captain::*;
+pub mod designate_captain;
+
+pub use forfeit::*;
+pub mod forfeit;
+
+pub use initialize_ffa_session::*;
+pub mod initialize_ffa_session;
+
+pub use join_ffa_session::*;
+pub mod join_ffa_session;
+
+pub use update_ffa_session_status::*;
+pub mod update_ffa_session_status;
+
+pub use payout_ffa::*;
+pub mod payout_ffa;
+
+pub use claim_ffa_refund::*;
+pub mod claim_ffa_refund;
+
+pub use close_ffa_session::*;
+pub mod close_ffa_session;
+
+pub use sponsor_deposit::*;
+pub mod sponsor_deposit;
+
+pub use refund_sponsor::*;
+pub mod refund_sponsor;
+
+pub use initialize_jackpot::*;
+pub mod initialize_jackpot;
+
+pub use award_jackpot::*;
+pub mod award_jackpot;
+
+pub use deposit_for_team::*;
+pub mod deposit_for_team;
+
+pub use deposit_for::*;
+pub mod deposit_for;
+
+pub use set_fee_recipients::*;
+pub mod set_fee_recipients;
+
+pub use set_referral_fee::*;
+pub mod set_referral_fee;
+
+pub use update_fee_schedule::*;
+pub mod update_fee_schedule;
+
+pub use set_fee_from_pot::*;
+pub mod set_fee_from_pot;
+
+pub use claim_referral::*;
+pub mod claim_referral;
+
+pub use claim_ffa_referral::*;
+pub mod claim_ffa_referral;
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::{GameSessionStatusError, TransferError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct PayoutFfa<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    player: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = player
+    )]
+    player_ata: InterfaceAccount<'info, TokenAccount>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"ffa_session", game.key().as_ref(), ffa_session.seed.to_le_bytes().as_ref()],
+        bump = ffa_session.bump,
+    )]
+    ffa_session: Box<Account<'info, FfaSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = ffa_session
+    )]
+    vault: InterfaceAccount <'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> PayoutFfa<'info> {
+    /// Pays an entrant the payout table share of their placement.
+    pub fn payout_ffa(&mut self) -> Result<()> {
+        require!(
+            self.ffa_session.status == GameSessionStatus::Ended,
+            GameSessionStatusError::InvalidStatus
+        );
+
+        let entrant = self.ffa_session
+            .find_entrant(self.player.key)
+            .ok_or(TransferError::PlayerNotEligibleForPayout)?;
+
+        let player = self.ffa_session.entrants[entrant];
+        require!(
+            player.paid && !player.refunded && !player.recieved_rewards,
+            TransferError::PlayerNotEligibleForPayout
+        );
+
+        let winnings = self.ffa_session.winnings(entrant)?;
+
+        transfer_from_vault(
+            &mut self.ffa_session,
+            &self.vault,
+            self.player_ata.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            winnings
+        )?;
+
+        self.ffa_session.entrants[entrant].paid = false;
+        self.ffa_session.entrants[entrant].recieved_rewards = true;
+
+        Ok(())
+    }
+}
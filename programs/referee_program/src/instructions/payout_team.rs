@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::{GameSessionStatusError, PlayerAccountsError, TransferError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct PayoutTeam<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> PayoutTeam<'info> {
+    /// Pays the winnings of every member of a winning team that has not received them yet.
+    /// `remaining_accounts` holds the token accounts of those players in seat order.
+    pub fn payout_team(&mut self, team_index: u8, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Ended,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            self.game_session.result.is_some(),
+            TransferError::ResultNotDeclared
+        );
+        require!(
+            team_index < self.game_session.amount_of_teams &&
+            self.game_session.is_winning_team(team_index as usize),
+            TransferError::TeamNotWinning
+        );
+
+        let i = team_index as usize;
+
+        let seats: Vec<usize> = (0..self.game_session.players_per_team[i] as usize)
+            .filter(|&j| {
+                let player = self.game_session.teams[i][j];
+                player.paid && !player.refunded && !player.recieved_rewards
+            })
+            .collect();
+
+        require!(
+            seats.len() == remaining_accounts.len(),
+            PlayerAccountsError::PlayerAccountsMismatch
+        );
+
+        for (&j, account) in seats.iter().zip(remaining_accounts) {
+            let player_ata = InterfaceAccount::<TokenAccount>::try_from(account)?;
+
+            require!(
+                player_ata.owner == self.game_session.teams[i][j].player &&
+                player_ata.mint == self.mint.key(),
+                PlayerAccountsError::InvalidPlayerAccount
+            );
+
+            let winnings = self.game_session.winnings(i, j)?;
+
+            transfer_from_vault(
+                &mut self.game_session,
+                &self.vault,
+                player_ata.to_account_info(),
+                &self.mint,
+                &self.token_program,
+                winnings
+            )?;
+
+            self.game_session.teams[i][j].paid = false;
+            self.game_session.teams[i][j].recieved_rewards = true;
+        }
+
+        Ok(())
+    }
+}
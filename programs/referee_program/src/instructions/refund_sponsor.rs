@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::SponsorError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RefundSponsor<'info> {
+    #[account(mut)]
+    sponsor: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sponsor
+    )]
+    sponsor_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        close = sponsor,
+        has_one = sponsor,
+        has_one = game_session,
+        seeds = [b"sponsorship", game_session.key().as_ref(), sponsor.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    sponsorship: Account<'info, Sponsorship>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RefundSponsor<'info> {
+    /// Returns a sponsorship once the session was cancelled or expired without a result.
+    pub fn refund_sponsor(&mut self) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Cancelled ||
+            self.game_session.has_expired(Clock::get()?.unix_timestamp),
+            SponsorError::RefundNotAvailable
+        );
+
+        let amount = self.sponsorship.amount;
+
+        transfer_from_vault(
+            &mut self.game_session,
+            &self.vault,
+            self.sponsor_ata.to_account_info(),
+            &self.mint,
+            &self.token_program,
+            amount
+        )?;
+
+        self.game_session.sponsored_amount = self.game_session.sponsored_amount
+            .checked_sub(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.game_session.sponsorships = self.game_session.sponsorships
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface}
+};
+
+use crate::errors::{DepositError, GameSessionStatusError, PlayerAccountsError, ReplacePlayerError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ReplacePlayer<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    /// Token account of whoever paid for the replaced player's seat, only needed when it was paid
+    #[account(mut)]
+    player_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ReplacePlayer<'info> {
+    /// Swaps the player in a seat before the match starts. A paid seat is refunded to
+    /// whoever paid for it first and the new player has to deposit themselves.
+    pub fn replace_player(&mut self, team_index: u8, seat_index: u8, new_player: Pubkey) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Initiated,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            team_index < self.game_session.amount_of_teams &&
+            seat_index < self.game_session.players_per_team[team_index as usize],
+            DepositError::InvalidSeat
+        );
+        require!(
+            new_player == Pubkey::default() || self.game_session.find_player(&new_player).is_none(),
+            ReplacePlayerError::PlayersNotUnique
+        );
+
+        let (i, j) = (team_index as usize, seat_index as usize);
+        let replaced = self.game_session.teams[i][j];
+
+        if replaced.paid {
+            let player_ata = self.player_ata
+                .as_ref()
+                .ok_or(ReplacePlayerError::PlayerAccountRequired)?;
+
+            require!(
+                player_ata.owner == replaced.payer && player_ata.mint == self.mint.key(),
+                PlayerAccountsError::InvalidPlayerAccount
+            );
+
+            transfer_from_vault(
+                &mut self.game_session,
+                &self.vault,
+                player_ata.to_account_info(),
+                &self.mint,
+                &self.token_program,
+                replaced.stake
+            )?;
+        }
+
+        self.game_session.teams[i][j] = Player {
+            player: new_player,
+            payer: Pubkey::default(),
+            stake: 0,
+            paid: false,
+            refunded: false,
+            recieved_rewards: false,
+            referrer: None,
+            referral_paid: false
+        };
+
+        // A captain who gives up their seat can no longer concede for the team
+        if self.game_session.captains[i] == replaced.player {
+            self.game_session.captains[i] = Pubkey::default();
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetFeeFromPot<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == ADMIN_PUBKEY
+    )]
+    authority: Signer<'info>,
+    game: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config", game.key().as_ref()],
+        bump = program_config.bump,
+    )]
+    program_config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> SetFeeFromPot<'info> {
+    /// Sessions created afterwards either take the fee from their prize pool alone or have
+    /// the game pre-fund it. Existing sessions settle the way they were created.
+    pub fn set_fee_from_pot(&mut self, fee_from_pot: bool) -> Result<()> {
+        self.program_config.fee_from_pot = fee_from_pot;
+
+        Ok(())
+    }
+}
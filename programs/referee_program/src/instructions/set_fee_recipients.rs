@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+use crate::errors::ProgramConfigCode;
+
+#[derive(Accounts)]
+pub struct SetFeeRecipients<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == ADMIN_PUBKEY
+    )]
+    authority: Signer<'info>,
+    game: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config", game.key().as_ref()],
+        bump = program_config.bump,
+    )]
+    program_config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> SetFeeRecipients<'info> {
+    /// Replaces the fee recipients. Recipients that stay in the list keep their total paid.
+    pub fn set_fee_recipients(&mut self, fee_shares: Vec<FeeShare>) -> Result<()> {
+        require!(
+            fee_shares.len() <= MAX_FEE_RECIPIENTS,
+            ProgramConfigCode::TooManyFeeRecipients
+        );
+
+        let total_basis_points = fee_shares
+            .iter()
+            .map(|share| share.basis_points as u64)
+            .sum::<u64>();
+
+        let unique = fee_shares
+            .iter()
+            .enumerate()
+            .all(|(k, share)| fee_shares[..k].iter().all(|other| other.recipient != share.recipient));
+
+        require!(
+            unique &&
+            total_basis_points <= 10000 &&
+            fee_shares.iter().all(|share| share.basis_points > 0),
+            ProgramConfigCode::InvalidFeeRecipients
+        );
+
+        let fee_recipients = fee_shares
+            .iter()
+            .map(|share| FeeRecipient {
+                recipient: share.recipient,
+                basis_points: share.basis_points,
+                total_paid: self.program_config.fee_recipients
+                    .iter()
+                    .find(|existing| existing.recipient == share.recipient)
+                    .map_or(0, |existing| existing.total_paid),
+            })
+            .collect();
+
+        self.program_config.fee_recipients = fee_recipients;
+
+        Ok(())
+    }
+}
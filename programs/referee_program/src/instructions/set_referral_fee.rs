@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+use crate::errors::ProgramConfigCode;
+
+#[derive(Accounts)]
+pub struct SetReferralFee<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == ADMIN_PUBKEY
+    )]
+    authority: Signer<'info>,
+    game: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config", game.key().as_ref()],
+        bump = program_config.bump,
+    )]
+    program_config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> SetReferralFee<'info> {
+    pub fn set_referral_fee(&mut self, referral_basis_points: u64) -> Result<()> {
+        require!(
+            referral_basis_points <= 10000,
+            ProgramConfigCode::ReferralBasisPointsTooHigh
+        );
+
+        self.program_config.referral_basis_points = referral_basis_points;
+
+        Ok(())
+    }
+}
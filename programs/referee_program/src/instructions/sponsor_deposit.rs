@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount,
+        TokenInterface,
+        TransferChecked,
+        transfer_checked
+    }
+};
+
+use crate::errors::{GameSessionStatusError, SponsorError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SponsorDeposit<'info> {
+    #[account(mut)]
+    sponsor: Signer<'info>,
+    game: SystemAccount<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sponsor
+    )]
+    sponsor_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = 8 + Sponsorship::INIT_SPACE,
+        seeds = [b"sponsorship", game_session.key().as_ref(), sponsor.key().as_ref()],
+        bump
+    )]
+    sponsorship: Account<'info, Sponsorship>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = game_session
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>
+}
+
+impl<'info> SponsorDeposit<'info> {
+    /// Tops up the prize pool of a session that has no result yet. Repeated deposits
+    /// from the same sponsor add up on their sponsorship. A session takes at most
+    /// `MAX_SPONSORSHIPS` sponsors, each opening with at least the cheapest seat's entry cost.
+    pub fn sponsor_deposit(&mut self, amount: u64, bumps: &SponsorDepositBumps) -> Result<()> {
+        require!(
+            matches!(
+                self.game_session.status,
+                GameSessionStatus::Initiated | GameSessionStatus::Started | GameSessionStatus::Ended
+            ) && self.game_session.result.is_none(),
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(amount > 0, SponsorError::InvalidAmount);
+
+        if self.sponsorship.amount == 0 {
+            require!(self.game_session.sponsorships < MAX_SPONSORSHIPS, SponsorError::TooManySponsorships);
+
+            let teams = self.game_session.amount_of_teams as usize;
+            let min_amount = self.game_session.session_entry_cost_per_player[..teams]
+                .iter()
+                .copied()
+                .min()
+                .unwrap_or_default();
+            require!(amount >= min_amount, SponsorError::AmountBelowMinimum);
+        }
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.sponsor_ata.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.sponsor.to_account_info(),
+            mint: self.mint.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        self.game_session.record_deposit(amount)?;
+        self.game_session.sponsored_amount = self.game_session.sponsored_amount
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if self.sponsorship.amount == 0 {
+            self.game_session.sponsorships = self.game_session.sponsorships
+                .checked_add(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        self.sponsorship.game_session = self.game_session.key();
+        self.sponsorship.sponsor = self.sponsor.key();
+        self.sponsorship.amount = self.sponsorship.amount
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.sponsorship.bump = bumps.sponsorship;
+
+        Ok(())
+    }
+}
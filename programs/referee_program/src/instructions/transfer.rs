@@ -1,15 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{
-        Mint, TokenAccount,
-        TokenInterface,
-        TransferChecked,
-        transfer_checked
-    }
+    token_interface::{Mint, TokenAccount, TokenInterface}
 };
 
-use crate::errors::TransferError;
+use crate::errors::{GameSessionStatusError, TransferError};
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -24,13 +19,18 @@ pub struct Transfer<'info> {
         associated_token::authority = player
     )]
     player_ata: InterfaceAccount<'info, TokenAccount>,
+    /// Token account of whoever paid for the seat, only needed to refund a seat the
+    /// player did not pay for
+    #[account(mut)]
+    payer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
     mint: InterfaceAccount<'info, Mint>,
     #[account(
         mut,
+        has_one = mint,
         seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
         bump,
     )]
-    game_session: Account<'info, GameSession>,
+    game_session: Box<Account<'info, GameSession>>,
     #[account(
         mut,
         associated_token::mint = mint,
@@ -43,125 +43,69 @@ pub struct Transfer<'info> {
 }
 
 impl<'info> Transfer<'info> {
-    fn transfer(&mut self, amount: u64) -> Result<()> {
-        let signer_seeds:[&[&[u8]]; 1] = [&[
-            b"game_session",
-            self.game.to_account_info().key.as_ref(),
-            &self.game_session.seed.to_le_bytes()[..],
-            &[self.game_session.bump]
-        ]];
-
-        let cpi_program = self.token_program.to_account_info();
-
-        let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            to: self.player_ata.to_account_info(),
-            authority: self.game_session.to_account_info(),
-            mint: self.mint.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            cpi_program,
-            cpi_accounts,
-            &signer_seeds
-        );
-
-        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
-
-        Ok(())
+    fn transfer(&mut self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_from_vault(
+            &mut self.game_session,
+            &self.vault,
+            to,
+            &self.mint,
+            &self.token_program,
+            amount
+        )
     }
 
-    //TODO: block payouts based on game session state
     pub fn payout_winning(&mut self) -> Result<()> {
-        let mut updated_teams = [[Player {
-            player: Pubkey::default(),
-            paid: false,
-            refunded: false,
-            recieved_rewards: false
-        }; MAX_PLAYERS_PER_TEAM]; MAX_TEAMS_LENGTH];
-
-        for i in 0..(self.game_session.amount_of_teams as usize) {
-            for j in 0..(self.game_session.players_per_team as usize) {
-                if self.game_session.teams[i][j].player == *self.player.to_account_info().key {
-                    require!(
-                        self.game_session.teams[i][j].paid &&
-                        !self.game_session.teams[i][j].refunded &&
-                        !self.game_session.teams[i][j].recieved_rewards,
-                        TransferError::PlayerNotEligibleForPayout
-                    );
+        let (i, j) = self.game_session
+            .find_player(self.player.key)
+            .ok_or(TransferError::PlayerNotEligibleForPayout)?;
 
-                    let winnings = self.game_session.session_entry_cost_per_team
-                        .checked_mul(self.game_session.amount_of_teams as u64)
-                        .ok_or(ProgramError::ArithmeticOverflow)?
-                        .checked_sub(self.game_session.termination_fee)
-                        .ok_or(ProgramError::ArithmeticOverflow)?
-                        .checked_div(self.game_session.players_per_team as u64)
-                        .ok_or(ProgramError::ArithmeticOverflow)?;
+        let winnings = self.game_session.winnings_due(i, j)?;
 
-                    self.transfer( winnings)?;
-
-                    updated_teams[i][j] = Player {
-                        player: self.game_session.teams[i][j].player,
-                        paid: false,
-                        refunded: false,
-                        recieved_rewards: true
-                    };
-                } else {
-                    updated_teams[i][j] = Player {
-                            player: self.game_session.teams[i][j].player,
-                            paid: self.game_session.teams[i][j].paid,
-                            refunded: self.game_session.teams[i][j].refunded,
-                            recieved_rewards: self.game_session.teams[i][j].recieved_rewards
-                        };
-                }
-            }
-        }
+        self.transfer(self.player_ata.to_account_info(), winnings)?;
 
-        self.game_session.teams = updated_teams;
+        self.game_session.teams[i][j].paid = false;
+        self.game_session.teams[i][j].recieved_rewards = true;
 
         Ok(())
     }
 
-    //TODO: block refunds based on game session state
     pub fn payout_refund(&mut self) -> Result<()> {
-        let mut updated_teams = [[Player {
-            player: Pubkey::default(),
-            paid: false,
-            refunded: false,
-            recieved_rewards: false
-        }; MAX_PLAYERS_PER_TEAM]; MAX_TEAMS_LENGTH];
+        require!(
+            self.game_session.status == GameSessionStatus::Initiated ||
+            self.game_session.status == GameSessionStatus::Started,
+            GameSessionStatusError::InvalidStatus
+        );
 
-        for i in 0..(self.game_session.amount_of_teams as usize) {
-            for j in 0..(self.game_session.players_per_team as usize) {
-                if self.game_session.teams[i][j].player == *self.player.to_account_info().key {
-                    require!(
-                        self.game_session.teams[i][j].paid &&
-                        !self.game_session.teams[i][j].refunded &&
-                        !self.game_session.teams[i][j].recieved_rewards,
-                        TransferError::PlayerNotEligibleForRefund
-                    );
+        let (i, j) = self.game_session
+            .find_player(self.player.key)
+            .ok_or(TransferError::PlayerNotEligibleForRefund)?;
 
-                    self.transfer(self.game_session.session_entry_cost_per_player)?;
+        let player = self.game_session.teams[i][j];
+        require!(
+            player.paid && !player.refunded && !player.recieved_rewards,
+            TransferError::PlayerNotEligibleForRefund
+        );
 
-                    updated_teams[i][j] = Player {
-                        player: self.game_session.teams[i][j].player,
-                        paid: false,
-                        refunded: true,
-                        recieved_rewards: false
-                    };
-                } else {
-                    updated_teams[i][j] = Player {
-                            player: self.game_session.teams[i][j].player,
-                            paid: self.game_session.teams[i][j].paid,
-                            refunded: self.game_session.teams[i][j].refunded,
-                            recieved_rewards: self.game_session.teams[i][j].recieved_rewards
-                        };
-                }
-            }
+        let refund_ata = player
+            .refund_account(&self.player_ata, self.payer_ata.as_ref())?
+            .to_account_info();
+
+        self.game_session.teams[i][j].paid = false;
+        self.game_session.teams[i][j].refunded = true;
+
+        // A running session still has to settle, so a refund may neither empty a team nor
+        // leave too little staked to cover the fee and the jackpot contribution
+        if self.game_session.status == GameSessionStatus::Started {
+            require!(
+                self.game_session.team(i).iter().any(|player| player.paid && !player.refunded),
+                TransferError::LastTeamMemberRefund
+            );
+            require!(
+                self.game_session.prize_pool().is_ok(),
+                TransferError::StakeBelowFees
+            );
         }
 
-        self.game_session.teams = updated_teams;
-
-        Ok(())
+        self.transfer(refund_ata, player.stake)
     }
 }
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+use crate::errors::ProgramConfigCode;
+
+#[derive(Accounts)]
+pub struct UpdateFeeSchedule<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == ADMIN_PUBKEY
+    )]
+    authority: Signer<'info>,
+    game: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config", game.key().as_ref()],
+        bump = program_config.bump,
+    )]
+    program_config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> UpdateFeeSchedule<'info> {
+    /// Sets the fee bounds and tiers applied to sessions created from now on.
+    pub fn update_fee_schedule(&mut self, min_fee: u64, max_fee: u64, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        require!(fee_tiers.len() <= MAX_FEE_TIERS, ProgramConfigCode::TooManyFeeTiers);
+        require!(
+            max_fee == 0 || min_fee <= max_fee,
+            ProgramConfigCode::InvalidFeeBounds
+        );
+
+        let jackpot_basis_points = self.program_config.jackpot_basis_points;
+
+        require!(
+            fee_tiers.windows(2).all(|pair| pair[0].min_pot < pair[1].min_pot) &&
+            fee_tiers.iter().all(|tier| {
+                tier.basis_points.checked_add(jackpot_basis_points).is_some_and(|total| total < 10000)
+            }),
+            ProgramConfigCode::InvalidFeeTiers
+        );
+
+        self.program_config.min_fee = min_fee;
+        self.program_config.max_fee = max_fee;
+        self.program_config.fee_tiers = fee_tiers;
+
+        Ok(())
+    }
+}
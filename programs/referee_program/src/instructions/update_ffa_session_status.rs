@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::{DeclareResultError, GameSessionStatusError};
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UpdateFfaSessionStatus<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"ffa_session", game.key().as_ref(), ffa_session.seed.to_le_bytes().as_ref()],
+        bump = ffa_session.bump
+    )]
+    ffa_session: Box<Account<'info, FfaSession>>,
+}
+
+impl<'info> UpdateFfaSessionStatus<'info> {
+    pub fn start_ffa_session(&mut self) -> Result<()> {
+        require!(
+            self.ffa_session.status == GameSessionStatus::Initiated,
+            GameSessionStatusError::InvalidStatus
+        );
+
+        require!(
+            self.ffa_session.is_fully_funded(),
+            GameSessionStatusError::PlayersNotPaid
+        );
+
+        self.ffa_session.status = GameSessionStatus::Started;
+
+        Ok(())
+    }
+
+    pub fn end_ffa_session(&mut self) -> Result<()> {
+        require!(
+            self.ffa_session.status == GameSessionStatus::Started,
+            GameSessionStatusError::InvalidStatus
+        );
+
+        self.ffa_session.status = GameSessionStatus::Ended;
+
+        Ok(())
+    }
+
+    /// Records the entrant indices of the paid placements, one per payout table share.
+    pub fn declare_ffa_placements(&mut self, placements: Vec<u8>) -> Result<()> {
+        require!(
+            self.ffa_session.status == GameSessionStatus::Ended,
+            GameSessionStatusError::InvalidStatus
+        );
+        require!(
+            !self.ffa_session.is_declared(),
+            DeclareResultError::ResultAlreadyDeclared
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.ffa_session.settlement_deadline,
+            DeclareResultError::SettlementDeadlinePassed
+        );
+        require!(
+            placements.len() == self.ffa_session.payout_table.len(),
+            DeclareResultError::InvalidPlacements
+        );
+
+        let mut placed: u64 = 0;
+        for &entrant in &placements {
+            require!(
+                (entrant as usize) < self.ffa_session.entrants.len() && placed & (1 << entrant) == 0,
+                DeclareResultError::InvalidPlacements
+            );
+            placed |= 1 << entrant;
+        }
+
+        self.ffa_session.placements = placements;
+
+        Ok(())
+    }
+}
@@ -24,11 +24,24 @@ pub struct UpdateProgramConfig<'info> {
 impl<'info> UpdateProgramConfig<'info> {
     pub fn update_program_config(
         &mut self,
-        fee_basis_points: u64
+        fee_basis_points: u64,
+        dust_recipient: Pubkey,
+        jackpot_basis_points: u64
     ) -> Result<()> {
         require!(fee_basis_points < 10000, ProgramConfigCode::FeeBasisPointsTooHigh);
+        require!(
+            fee_basis_points.checked_add(jackpot_basis_points).is_some_and(|total| total < 10000) &&
+            self.program_config.fee_tiers
+                .iter()
+                .all(|tier| {
+                    tier.basis_points.checked_add(jackpot_basis_points).is_some_and(|total| total < 10000)
+                }),
+            ProgramConfigCode::JackpotBasisPointsTooHigh
+        );
 
         self.program_config.fee_basis_points = fee_basis_points;
+        self.program_config.dust_recipient = dust_recipient;
+        self.program_config.jackpot_basis_points = jackpot_basis_points;
         
         Ok(())
     }
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::GameSessionStatusError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UpdateSessionStatus<'info> {
+    #[account(mut)]
+    game: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game_session", game.key().as_ref(), game_session.seed.to_le_bytes().as_ref()],
+        bump = game_session.bump
+    )]
+    game_session: Box<Account<'info, GameSession>>,
+}
+
+impl<'info> UpdateSessionStatus<'info> {
+    pub fn start_session(&mut self) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Initiated,
+            GameSessionStatusError::InvalidStatus
+        );
+
+        require!(
+            self.game_session.is_fully_funded(),
+            GameSessionStatusError::PlayersNotPaid
+        );
+
+        self.game_session.status = GameSessionStatus::Started;
+
+        Ok(())
+    }
+
+    pub fn end_session(&mut self) -> Result<()> {
+        require!(
+            self.game_session.status == GameSessionStatus::Started,
+            GameSessionStatusError::InvalidStatus
+        );
+
+        self.game_session.status = GameSessionStatus::Ended;
+
+        Ok(())
+    }
+}
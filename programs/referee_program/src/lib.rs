@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::instructions::*;
+use crate::state::{FeeShare, FeeTier, GameSessionResult};
 
 pub mod errors;
+pub mod events;
 pub mod state;
 pub mod instructions;
 
@@ -12,19 +14,96 @@ declare_id!("6f4JGkiVCkyLm7gReznbihsRaFv1bTwSaiftGLB4UFVm");
 pub mod referee_program {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_session(
         ctx: Context<InitializeGameSession>,
         seed: u64,
         session_entry_cost_per_team: u64,
-        teams: Vec<Vec<Pubkey>>
+        teams: Vec<Vec<Pubkey>>,
+        join_deadline: i64,
+        settlement_deadline: i64,
+        game_assigns_seats: bool,
+        payout_table: Vec<u16>,
+        allow_unequal_teams: bool,
+        variable_stakes: bool
     ) -> Result<()> {
-        ctx.accounts.initialize_game_session(seed, session_entry_cost_per_team, teams, &ctx.bumps)?;
+        ctx.accounts.initialize_game_session(
+            seed,
+            session_entry_cost_per_team,
+            teams,
+            join_deadline,
+            settlement_deadline,
+            game_assigns_seats,
+            payout_table,
+            allow_unequal_teams,
+            variable_stakes,
+            &ctx.bumps
+        )?;
         ctx.accounts.deposit_termination_fee()?;
         Ok(())
     }
 
-    pub fn deposit(ctx: Context<Deposit>) -> Result<()> {
-        ctx.accounts.deposit_entry_fee()
+    pub fn start_session(ctx: Context<UpdateSessionStatus>) -> Result<()> {
+        ctx.accounts.start_session()
+    }
+
+    pub fn end_session(ctx: Context<UpdateSessionStatus>) -> Result<()> {
+        ctx.accounts.end_session()
+    }
+
+    pub fn declare_result(ctx: Context<DeclareResult>, result: GameSessionResult) -> Result<()> {
+        ctx.accounts.declare_result(result)
+    }
+
+    pub fn designate_captain(ctx: Context<DesignateCaptain>, team_index: u8, captain: Pubkey) -> Result<()> {
+        ctx.accounts.designate_captain(team_index, captain)
+    }
+
+    pub fn forfeit(ctx: Context<Forfeit>, team_index: u8) -> Result<()> {
+        ctx.accounts.forfeit(team_index)
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, stake: Option<u64>, referrer: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.deposit_entry_fee(stake, referrer)
+    }
+
+    pub fn join_open_seat(
+        ctx: Context<Deposit>,
+        team_index: u8,
+        seat_index: u8,
+        stake: Option<u64>,
+        referrer: Option<Pubkey>
+    ) -> Result<()> {
+        ctx.accounts.deposit_into_open_seat(team_index, seat_index, stake, referrer)
+    }
+
+    pub fn deposit_for(ctx: Context<DepositFor>, stake: Option<u64>) -> Result<()> {
+        ctx.accounts.deposit_for(stake)
+    }
+
+    pub fn deposit_for_team(ctx: Context<DepositForTeam>, team_index: u8) -> Result<()> {
+        ctx.accounts.deposit_for_team(team_index)
+    }
+
+    pub fn sponsor_deposit(ctx: Context<SponsorDeposit>, amount: u64) -> Result<()> {
+        ctx.accounts.sponsor_deposit(amount, &ctx.bumps)
+    }
+
+    pub fn refund_sponsor(ctx: Context<RefundSponsor>) -> Result<()> {
+        ctx.accounts.refund_sponsor()
+    }
+
+    pub fn leave_session(ctx: Context<LeaveSession>) -> Result<()> {
+        ctx.accounts.leave_session()
+    }
+
+    pub fn replace_player(
+        ctx: Context<ReplacePlayer>,
+        team_index: u8,
+        seat_index: u8,
+        new_player: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.replace_player(team_index, seat_index, new_player)
     }
 
     pub fn refund(ctx: Context<Transfer>) -> Result<()> {
@@ -35,15 +114,139 @@ pub mod referee_program {
         ctx.accounts.payout_winning()
     }
 
-    pub fn close(ctx: Context<Close>) -> Result<()> {
-        ctx.accounts.close_game_session()
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        ctx.accounts.claim_refund()
+    }
+
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        ctx.accounts.claim_winnings()
+    }
+
+    pub fn cancel_session<'info>(ctx: Context<'_, '_, 'info, 'info, CancelSession<'info>>) -> Result<()> {
+        ctx.accounts.cancel_session(ctx.remaining_accounts)
+    }
+
+    pub fn payout_team<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PayoutTeam<'info>>,
+        team_index: u8
+    ) -> Result<()> {
+        ctx.accounts.payout_team(team_index, ctx.remaining_accounts)
+    }
+
+    pub fn close<'info>(ctx: Context<'_, '_, 'info, 'info, Close<'info>>) -> Result<()> {
+        ctx.accounts.close_game_session(ctx.remaining_accounts)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_ffa_session(
+        ctx: Context<InitializeFfaSession>,
+        seed: u64,
+        entry_cost_per_player: u64,
+        max_entrants: u8,
+        join_deadline: i64,
+        settlement_deadline: i64,
+        payout_table: Vec<u16>
+    ) -> Result<()> {
+        ctx.accounts.initialize_ffa_session(
+            seed,
+            entry_cost_per_player,
+            max_entrants,
+            join_deadline,
+            settlement_deadline,
+            payout_table,
+            &ctx.bumps
+        )?;
+        ctx.accounts.deposit_termination_fee()?;
+        Ok(())
+    }
+
+    pub fn join_ffa_session(ctx: Context<JoinFfaSession>, referrer: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.join_ffa_session(referrer)
+    }
+
+    pub fn start_ffa_session(ctx: Context<UpdateFfaSessionStatus>) -> Result<()> {
+        ctx.accounts.start_ffa_session()
+    }
+
+    pub fn end_ffa_session(ctx: Context<UpdateFfaSessionStatus>) -> Result<()> {
+        ctx.accounts.end_ffa_session()
+    }
+
+    pub fn declare_ffa_placements(ctx: Context<UpdateFfaSessionStatus>, placements: Vec<u8>) -> Result<()> {
+        ctx.accounts.declare_ffa_placements(placements)
+    }
+
+    pub fn payout_ffa(ctx: Context<PayoutFfa>) -> Result<()> {
+        ctx.accounts.payout_ffa()
+    }
+
+    pub fn claim_ffa_refund(ctx: Context<ClaimFfaRefund>) -> Result<()> {
+        ctx.accounts.claim_ffa_refund()
+    }
+
+    pub fn close_ffa_session<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseFfaSession<'info>>
+    ) -> Result<()> {
+        ctx.accounts.close_ffa_session(ctx.remaining_accounts)
+    }
+
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        fee_basis_points: u64,
+        dust_recipient: Pubkey,
+        jackpot_basis_points: u64
+    ) -> Result<()> {
+        ctx.accounts.initialize_program_config(
+            fee_basis_points,
+            dust_recipient,
+            jackpot_basis_points,
+            &ctx.bumps
+        )
+    }
+
+    pub fn update_program_config(
+        ctx: Context<UpdateProgramConfig>,
+        fee_basis_points: u64,
+        dust_recipient: Pubkey,
+        jackpot_basis_points: u64
+    ) -> Result<()> {
+        ctx.accounts.update_program_config(fee_basis_points, dust_recipient, jackpot_basis_points)
+    }
+
+    pub fn update_fee_schedule(
+        ctx: Context<UpdateFeeSchedule>,
+        min_fee: u64,
+        max_fee: u64,
+        fee_tiers: Vec<FeeTier>
+    ) -> Result<()> {
+        ctx.accounts.update_fee_schedule(min_fee, max_fee, fee_tiers)
+    }
+
+    pub fn set_fee_from_pot(ctx: Context<SetFeeFromPot>, fee_from_pot: bool) -> Result<()> {
+        ctx.accounts.set_fee_from_pot(fee_from_pot)
+    }
+
+    pub fn set_referral_fee(ctx: Context<SetReferralFee>, referral_basis_points: u64) -> Result<()> {
+        ctx.accounts.set_referral_fee(referral_basis_points)
+    }
+
+    pub fn claim_referral(ctx: Context<ClaimReferral>) -> Result<()> {
+        ctx.accounts.claim_referral()
+    }
+
+    pub fn claim_ffa_referral(ctx: Context<ClaimFfaReferral>) -> Result<()> {
+        ctx.accounts.claim_ffa_referral()
+    }
+
+    pub fn set_fee_recipients(ctx: Context<SetFeeRecipients>, fee_shares: Vec<FeeShare>) -> Result<()> {
+        ctx.accounts.set_fee_recipients(fee_shares)
     }
 
-    pub fn initialize_program_config(ctx: Context<InitializeProgramConfig>, fee_basis_points: u64) -> Result<()> {
-        ctx.accounts.initialize_program_config(fee_basis_points, &ctx.bumps)
+    pub fn initialize_jackpot(ctx: Context<InitializeJackpot>) -> Result<()> {
+        ctx.accounts.initialize_jackpot(&ctx.bumps)
     }
 
-    pub fn update_program_config(ctx: Context<UpdateProgramConfig>, fee_basis_points: u64) -> Result<()> {
-        ctx.accounts.update_program_config(fee_basis_points)
+    pub fn award_jackpot(ctx: Context<AwardJackpot>) -> Result<()> {
+        ctx.accounts.award_jackpot()
     }
 }
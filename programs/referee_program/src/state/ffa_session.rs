@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::{FfaSessionError, TransferError};
+
+use super::{GameSessionStatus, Player};
+
+pub const MAX_FFA_ENTRANTS: usize = 64;
+
+/// Free-for-all sessions stay out of the game's jackpot, their prize pool is the stakes less
+/// the termination fee
+#[account]
+#[derive(InitSpace)]
+pub struct FfaSession {
+    pub seed: u64,
+    pub game: Pubkey,
+    pub mint: Pubkey,
+    pub entry_cost_per_player: u64,
+    pub max_entrants: u8,
+    #[max_len(MAX_FFA_ENTRANTS)]
+    pub entrants: Vec<Player>,
+    /// Share of the prize pool in basis points for each paid placement, first place first
+    #[max_len(MAX_FFA_ENTRANTS)]
+    pub payout_table: Vec<u16>,
+    /// Entrant indices of the paid placements, first place first. Empty until declared
+    #[max_len(MAX_FFA_ENTRANTS)]
+    pub placements: Vec<u8>,
+    pub termination_fee: u64,
+    /// Whether the game pre-funded the termination fee into the vault as collateral
+    pub termination_fee_paid: bool,
+    /// Snapshot of the program config's referral share when the session was created
+    pub referral_basis_points: u64,
+    /// Balance the vault should hold given every transfer the program made
+    pub vault_balance: u64,
+    pub status: GameSessionStatus,
+    pub join_deadline: i64,
+    pub settlement_deadline: i64,
+    pub bump: u8,
+}
+
+impl FfaSession {
+    pub fn find_entrant(&self, player: &Pubkey) -> Option<usize> {
+        self.entrants.iter().position(|entrant| entrant.player == *player)
+    }
+
+    pub fn is_fully_funded(&self) -> bool {
+        self.entrants.len() == self.max_entrants as usize &&
+            self.entrants.iter().all(|entrant| entrant.paid)
+    }
+
+    /// Indices of every referred entrant that was not refunded, in join order
+    pub fn referred_entrants(&self) -> impl Iterator<Item = usize> + '_ {
+        self.entrants
+            .iter()
+            .enumerate()
+            .filter(|(_, entrant)| entrant.referrer.is_some() && !entrant.refunded)
+            .map(|(k, _)| k)
+    }
+
+    /// Referral owed on an entrant's part of the termination fee
+    pub fn referral_fee(&self, entrant: usize) -> Result<u64> {
+        self.entrants[entrant].referral_fee(
+            self.termination_fee,
+            self.total_stake()?,
+            self.referral_basis_points
+        )
+    }
+
+    /// Referrals owed on every referred entrant, and the part of them not claimed yet
+    pub fn referral_fees(&self) -> Result<(u64, u64)> {
+        self.referred_entrants().try_fold((0_u64, 0_u64), |(owed, unclaimed), k| {
+            let referral = self.referral_fee(k)?;
+            let unclaimed_referral = if self.entrants[k].referral_paid { 0 } else { referral };
+
+            Ok((
+                owed.checked_add(referral).ok_or(ProgramError::ArithmeticOverflow)?,
+                unclaimed.checked_add(unclaimed_referral).ok_or(ProgramError::ArithmeticOverflow)?
+            ))
+        })
+    }
+
+    pub fn total_stake(&self) -> Result<u64> {
+        Ok(self.entry_cost_per_player
+            .checked_mul(self.max_entrants as u64)
+            .ok_or(ProgramError::ArithmeticOverflow)?)
+    }
+
+    pub fn is_declared(&self) -> bool {
+        !self.placements.is_empty()
+    }
+
+    pub fn prize_pool(&self) -> Result<u64> {
+        Ok(self.total_stake()?
+            .checked_sub(self.termination_fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?)
+    }
+
+    pub fn winnings(&self, entrant: usize) -> Result<u64> {
+        require!(self.is_declared(), TransferError::ResultNotDeclared);
+
+        let place = self.placements
+            .iter()
+            .position(|&placed| placed as usize == entrant)
+            .ok_or(FfaSessionError::EntrantNotPlaced)?;
+
+        let winnings = (self.prize_pool()? as u128)
+            .checked_mul(self.payout_table[place] as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(10_000)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(winnings as u64)
+    }
+
+    /// Keeps `vault_balance` in step with the program's transfers in and out of the vault
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.vault_balance = self.vault_balance
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        self.vault_balance = self.vault_balance
+            .checked_sub(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
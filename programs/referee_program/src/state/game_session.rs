@@ -1,10 +1,30 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::{DepositError, GameSessionStatusError, TransferError};
+
 use super::Player;
 
-pub const MAX_TEAMS_LENGTH: usize = 2;
+pub const MAX_TEAMS_LENGTH: usize = 8;
 pub const MAX_PLAYERS_PER_TEAM: usize = 5;
 
+/// Closing a session deletes its account, so there is no status to observe after `Ended`
+/// or `Cancelled`
+#[derive(InitSpace, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum GameSessionStatus {
+    Initiated,
+    Started,
+    Ended,
+    Cancelled,
+}
+
+#[derive(InitSpace, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum GameSessionResult {
+    Winner { team: u8 },
+    Draw,
+    /// `placements[k]` is the team that finished in place `k`, entries past `amount_of_teams` are ignored
+    Ranked { placements: [u8; MAX_TEAMS_LENGTH] },
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct GameSession {
@@ -12,12 +32,239 @@ pub struct GameSession {
     pub game: Pubkey,
     pub mint: Pubkey,
     pub session_entry_cost_per_team: u64,
-    pub session_entry_cost_per_player: u64,
+    /// Each team stakes `session_entry_cost_per_team`, split across its own players
+    pub session_entry_cost_per_player: [u64; MAX_TEAMS_LENGTH],
     pub amount_of_teams: u8,
-    pub players_per_team: u8,
+    pub players_per_team: [u8; MAX_TEAMS_LENGTH],
     pub teams: [[Player; MAX_PLAYERS_PER_TEAM]; MAX_TEAMS_LENGTH],
+    pub game_assigns_seats: bool,
+    /// Players may stake more than the entry cost and win in proportion to their stake
+    pub variable_stakes: bool,
+    /// Share of the prize pool in basis points for each placement, first place first
+    pub payout_table: [u16; MAX_TEAMS_LENGTH],
+    pub captains: [Pubkey; MAX_TEAMS_LENGTH],
+    /// Bit `j` of a team's entry is set once the player in seat `j` signed a forfeit
+    pub forfeit_votes: [u8; MAX_TEAMS_LENGTH],
     pub termination_fee: u64,
+    /// Whether the game pre-funded the termination fee into the vault as collateral
     pub termination_fee_paid: bool,
+    /// Sponsor top-ups added to the prize pool, refunded to the sponsors if the session is not settled
+    pub sponsored_amount: u64,
+    /// Sponsorship accounts still open for this session, at most `MAX_SPONSORSHIPS`, settled when it closes
+    pub sponsorships: u16,
+    /// Snapshot of the program config's jackpot share when the session was created
+    pub jackpot_basis_points: u64,
+    /// Snapshot of the program config's referral share when the session was created
+    pub referral_basis_points: u64,
+    /// Jackpot awarded to this session, added to the prize pool
+    pub jackpot_awarded: u64,
+    /// Balance the vault should hold given every transfer the program made
+    pub vault_balance: u64,
+    pub status: GameSessionStatus,
+    pub result: Option<GameSessionResult>,
+    pub join_deadline: i64,
+    pub settlement_deadline: i64,
     pub bump: u8,
-    //TODO: add field for game_status = INITIATED, STARTED, ENDED, CLOSED
+}
+
+impl GameSession {
+    pub fn team(&self, team: usize) -> &[Player] {
+        &self.teams[team][..self.players_per_team[team] as usize]
+    }
+
+    pub fn seats(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.amount_of_teams as usize)
+            .flat_map(move |i| (0..self.players_per_team[i] as usize).map(move |j| (i, j)))
+    }
+
+    pub fn find_player(&self, player: &Pubkey) -> Option<(usize, usize)> {
+        self.seats().find(|&(i, j)| self.teams[i][j].player == *player)
+    }
+
+    pub fn is_fully_funded(&self) -> bool {
+        self.seats().all(|(i, j)| self.teams[i][j].paid)
+    }
+
+    /// Every referred seat that was not refunded, in seat order
+    pub fn referred_seats(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.seats().filter(|&(i, j)| {
+            let player = &self.teams[i][j];
+
+            player.referrer.is_some() && !player.refunded
+        })
+    }
+
+    /// Referral owed on a seat's part of the termination fee
+    pub fn referral_fee(&self, team: usize, seat: usize) -> Result<u64> {
+        self.teams[team][seat].referral_fee(
+            self.termination_fee,
+            self.total_stake()?,
+            self.referral_basis_points
+        )
+    }
+
+    /// Referrals owed on every referred seat, and the part of them not claimed yet
+    pub fn referral_fees(&self) -> Result<(u64, u64)> {
+        self.referred_seats().try_fold((0_u64, 0_u64), |(owed, unclaimed), (i, j)| {
+            let referral = self.referral_fee(i, j)?;
+            let unclaimed_referral = if self.teams[i][j].referral_paid { 0 } else { referral };
+
+            Ok((
+                owed.checked_add(referral).ok_or(ProgramError::ArithmeticOverflow)?,
+                unclaimed.checked_add(unclaimed_referral).ok_or(ProgramError::ArithmeticOverflow)?
+            ))
+        })
+    }
+
+    /// The session failed to fill up before its join deadline, or no result was declared
+    /// before its settlement deadline.
+    pub fn has_expired(&self, now: i64) -> bool {
+        let join_expired = self.status == GameSessionStatus::Initiated &&
+            now >= self.join_deadline &&
+            !self.is_fully_funded();
+
+        let settlement_expired = self.result.is_none() && now >= self.settlement_deadline;
+
+        join_expired || settlement_expired
+    }
+
+    /// Players pay the team's entry cost per player, or choose a larger stake when the
+    /// session was created with `variable_stakes`.
+    pub fn entry_stake(&self, team: usize, stake: Option<u64>) -> Result<u64> {
+        let entry_cost = self.session_entry_cost_per_player[team];
+
+        match stake {
+            None => Ok(entry_cost),
+            Some(stake) => {
+                require!(self.variable_stakes, DepositError::VariableStakesDisabled);
+                require!(stake >= entry_cost, DepositError::StakeBelowEntryCost);
+
+                Ok(stake)
+            }
+        }
+    }
+
+    /// Stake of every player in the team that was not refunded
+    pub fn team_stake(&self, team: usize) -> Result<u64> {
+        self.team(team)
+            .iter()
+            .filter(|player| !player.refunded)
+            .try_fold(0_u64, |total, player| total.checked_add(player.stake))
+            .ok_or(ProgramError::ArithmeticOverflow.into())
+    }
+
+    pub fn total_stake(&self) -> Result<u64> {
+        (0..self.amount_of_teams as usize).try_fold(0_u64, |total, team| {
+            Ok(total
+                .checked_add(self.team_stake(team)?)
+                .ok_or(ProgramError::ArithmeticOverflow)?)
+        })
+    }
+
+    /// Share of the stakes that feeds the jackpot, rounded down to the nearest base unit
+    pub fn jackpot_contribution(&self) -> Result<u64> {
+        let contribution = (self.total_stake()? as u128)
+            .checked_mul(self.jackpot_basis_points as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(10_000)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(contribution as u64)
+    }
+
+    pub fn prize_pool(&self) -> Result<u64> {
+        Ok(self.total_stake()?
+            .checked_sub(self.termination_fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_sub(self.jackpot_contribution()?)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_add(self.sponsored_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_add(self.jackpot_awarded)
+            .ok_or(ProgramError::ArithmeticOverflow)?)
+    }
+
+    pub fn placement(&self, team: usize) -> Option<usize> {
+        match self.result {
+            Some(GameSessionResult::Ranked { placements }) => placements[..self.amount_of_teams as usize]
+                .iter()
+                .position(|&placed| placed as usize == team),
+            _ => None,
+        }
+    }
+
+    pub fn is_winning_team(&self, team: usize) -> bool {
+        match self.result {
+            Some(GameSessionResult::Winner { team: winning_team }) => winning_team as usize == team,
+            Some(GameSessionResult::Draw) => true,
+            Some(GameSessionResult::Ranked { .. }) => self.placement(team)
+                .is_some_and(|place| self.payout_table[place] > 0),
+            None => false,
+        }
+    }
+
+    /// A win pays the prize pool to the winning team and ranked results pay each team its
+    /// placement's share of the payout table, split across the team by stake. A draw splits
+    /// the prize pool by stake across every player.
+    pub fn winnings(&self, team: usize, seat: usize) -> Result<u64> {
+        let (prize, staked) = match self.result {
+            Some(GameSessionResult::Winner { .. }) => (self.prize_pool()?, self.team_stake(team)?),
+            Some(GameSessionResult::Draw) => (self.prize_pool()?, self.total_stake()?),
+            Some(GameSessionResult::Ranked { .. }) => {
+                let place = self.placement(team).ok_or(TransferError::PlayerNotInWinningTeam)?;
+
+                let team_prize = (self.prize_pool()? as u128)
+                    .checked_mul(self.payout_table[place] as u128)
+                    .ok_or(ProgramError::ArithmeticOverflow)?
+                    .checked_div(10_000)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+                (team_prize as u64, self.team_stake(team)?)
+            },
+            None => return err!(TransferError::ResultNotDeclared),
+        };
+
+        let winnings = (prize as u128)
+            .checked_mul(self.teams[team][seat].stake as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(staked as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(winnings as u64)
+    }
+
+    /// Winnings owed to a seat of a winning team once the result is declared
+    pub fn winnings_due(&self, team: usize, seat: usize) -> Result<u64> {
+        require!(
+            self.status == GameSessionStatus::Ended,
+            GameSessionStatusError::InvalidStatus
+        );
+
+        let player = self.teams[team][seat];
+        require!(
+            player.paid && !player.refunded && !player.recieved_rewards,
+            TransferError::PlayerNotEligibleForPayout
+        );
+        require!(self.result.is_some(), TransferError::ResultNotDeclared);
+        require!(self.is_winning_team(team), TransferError::PlayerNotInWinningTeam);
+
+        self.winnings(team, seat)
+    }
+
+    /// Keeps `vault_balance` in step with the program's transfers in and out of the vault
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.vault_balance = self.vault_balance
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        self.vault_balance = self.vault_balance
+            .checked_sub(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
 }
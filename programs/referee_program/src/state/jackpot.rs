@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Rollover pool of a game for one mint, its tokens are held by the jackpot's token account
+#[account]
+#[derive(InitSpace)]
+pub struct Jackpot {
+    pub game: Pubkey,
+    pub mint: Pubkey,
+    pub bump: u8,
+}
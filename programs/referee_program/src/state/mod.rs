@@ -6,3 +6,15 @@ pub mod player;
 
 pub use program_config::*;
 pub mod program_config;
+
+pub use ffa_session::*;
+pub mod ffa_session;
+
+pub use sponsorship::*;
+pub mod sponsorship;
+
+pub use jackpot::*;
+pub mod jackpot;
+
+pub use vault::*;
+pub mod vault;
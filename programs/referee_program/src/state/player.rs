@@ -1,9 +1,57 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
-#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, Copy)]
+use crate::errors::PlayerAccountsError;
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, Copy, Default)]
 pub struct Player {
     pub player: Pubkey,
+    /// Account that paid the stake, the player themselves unless their team paid for them
+    pub payer: Pubkey,
+    /// Amount the player deposited, refunded in full and used to weigh their winnings
+    pub stake: u64,
     pub paid: bool,
     pub refunded: bool,
     pub recieved_rewards: bool,
+    /// Account credited with bringing the player in, paid a share of the termination fee
+    pub referrer: Option<Pubkey>,
+    /// Whether the referrer claimed their share of this seat's part of the fee
+    pub referral_paid: bool,
+}
+
+impl Player {
+    /// Referrer's share of the part of a session's fee attributed to this seat by its
+    /// stake, rounded down to the nearest base unit
+    pub fn referral_fee(&self, fee: u64, total_stake: u64, referral_basis_points: u64) -> Result<u64> {
+        let referral = (fee as u128)
+            .checked_mul(self.stake as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_mul(referral_basis_points as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div((total_stake as u128) * 10_000)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(referral as u64)
+    }
+
+    /// Token account a refund of this seat goes to. Seats paid for by someone else are
+    /// refunded to the payer, whose token account then has to be passed as `payer_ata`.
+    pub fn refund_account<'a, 'info>(
+        &self,
+        player_ata: &'a InterfaceAccount<'info, TokenAccount>,
+        payer_ata: Option<&'a InterfaceAccount<'info, TokenAccount>>
+    ) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+        if self.payer == self.player {
+            return Ok(player_ata);
+        }
+
+        let payer_ata = payer_ata.ok_or(PlayerAccountsError::PayerAccountRequired)?;
+
+        require!(
+            payer_ata.owner == self.payer && payer_ata.mint == player_ata.mint,
+            PlayerAccountsError::InvalidPlayerAccount
+        );
+
+        Ok(payer_ata)
+    }
 }
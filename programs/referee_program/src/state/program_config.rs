@@ -1,12 +1,145 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::GameSessionCloseError;
 
 pub const ADMIN_PUBKEY: Pubkey = pubkey!("janrWJ8kCUrwkQEfgaPtzK3ZLE5HnBzrCA6KaW5f6rH");
+pub const MAX_FEE_RECIPIENTS: usize = 4;
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Fee rate for pots of at least `min_pot` base units
+#[derive(InitSpace, Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub struct FeeTier {
+    pub min_pot: u64,
+    pub basis_points: u64,
+}
+
+/// Share of the termination fee the admin assigns to a recipient
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub struct FeeShare {
+    pub recipient: Pubkey,
+    pub basis_points: u16,
+}
+
+#[derive(InitSpace, Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub struct FeeRecipient {
+    pub recipient: Pubkey,
+    /// Share of every termination fee, in basis points of the fee
+    pub basis_points: u16,
+    /// Fees paid out to the recipient so far
+    pub total_paid: u64,
+}
 
 #[account]
 #[derive(InitSpace)]
 pub struct ProgramConfig {
     pub admin: Pubkey,
     pub protocol_ata: Pubkey,
+    /// Wallet whose token account receives whatever is left in a vault when a session closes
+    pub dust_recipient: Pubkey,
     pub fee_basis_points: u64,
+    /// Smallest termination fee in base units
+    pub min_fee: u64,
+    /// Largest termination fee in base units, zero leaves the fee uncapped
+    pub max_fee: u64,
+    /// Fee rates by pot size, sorted by `min_pot`. The highest tier a pot reaches
+    /// replaces `fee_basis_points`
+    #[max_len(MAX_FEE_TIERS)]
+    pub fee_tiers: Vec<FeeTier>,
+    /// Slice of every settled team session's stakes that feeds the game's jackpot. Free-for-all
+    /// sessions neither feed the jackpot nor can be awarded it
+    pub jackpot_basis_points: u64,
+    /// Recipients sharing the fee of settled sessions, the protocol keeps the rest
+    #[max_len(MAX_FEE_RECIPIENTS)]
+    pub fee_recipients: Vec<FeeRecipient>,
+    /// Share of a referred player's part of the termination fee that their referrer can claim,
+    /// set aside before the fee recipients' shares
+    pub referral_basis_points: u64,
+    /// Take the termination fee from the prize pool alone instead of having the game pre-fund it
+    pub fee_from_pot: bool,
     pub bump: u8,
 }
+
+impl ProgramConfig {
+    pub fn fee_basis_points_for(&self, pot: u64) -> u64 {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| pot >= tier.min_pot)
+            .map_or(self.fee_basis_points, |tier| tier.basis_points)
+    }
+
+    /// Fee on a pot of base units at the rate of its tier, rounded down to the nearest
+    /// base unit and kept between the minimum and maximum fee.
+    pub fn termination_fee(&self, pot: u64) -> Result<u64> {
+        let fee = (pot as u128)
+            .checked_mul(self.fee_basis_points_for(pot) as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(10_000)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let fee = u64::try_from(fee)
+            .map_err(|_| ProgramError::ArithmeticOverflow)?
+            .max(self.min_fee);
+
+        Ok(if self.max_fee > 0 { fee.min(self.max_fee) } else { fee })
+    }
+
+    /// Each fee recipient's share of a termination fee, rounded down to the nearest base unit.
+    pub fn fee_share(&self, fee: u64, recipient: usize) -> Result<u64> {
+        let share = (fee as u128)
+            .checked_mul(self.fee_recipients[recipient].basis_points as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(10_000)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(share as u64)
+    }
+
+    /// Splits a settled session's fee, less the referrals set aside for its referrers,
+    /// between the fee recipients and the protocol. `accounts` holds the recipients' token
+    /// accounts in config order. Records what each recipient is owed and returns every
+    /// payout along with the protocol's remainder.
+    pub fn split_fee<'info>(
+        &mut self,
+        fee: u64,
+        mint: Pubkey,
+        accounts: &'info [AccountInfo<'info>]
+    ) -> Result<(Vec<(AccountInfo<'info>, u64)>, u64)> {
+        require!(
+            accounts.len() == self.fee_recipients.len(),
+            GameSessionCloseError::FeeRecipientAccountsMismatch
+        );
+
+        let mut payouts = Vec::with_capacity(accounts.len());
+        let mut protocol_fee = fee;
+
+        for (k, account) in accounts.iter().enumerate() {
+            let recipient_ata = InterfaceAccount::<TokenAccount>::try_from(account)?;
+
+            require!(
+                recipient_ata.owner == self.fee_recipients[k].recipient && recipient_ata.mint == mint,
+                GameSessionCloseError::InvalidFeeRecipientAccount
+            );
+
+            let share = self.fee_share(fee, k)?;
+
+            self.record_fee_paid(k, share)?;
+
+            protocol_fee = protocol_fee.checked_sub(share).ok_or(ProgramError::ArithmeticOverflow)?;
+            payouts.push((account.clone(), share));
+        }
+
+        Ok((payouts, protocol_fee))
+    }
+
+    pub fn record_fee_paid(&mut self, recipient: usize, amount: u64) -> Result<()> {
+        let fee_recipient = &mut self.fee_recipients[recipient];
+
+        fee_recipient.total_paid = fee_recipient.total_paid
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
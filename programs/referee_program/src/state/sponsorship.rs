@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// `close` settles every open sponsorship in one transaction, so their number is capped
+pub const MAX_SPONSORSHIPS: u16 = 4;
+
+/// What a sponsor added to a session's prize pool, so it can be refunded if the session is not settled
+#[account]
+#[derive(InitSpace)]
+pub struct Sponsorship {
+    pub game_session: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
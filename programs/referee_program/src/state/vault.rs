@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked
+};
+
+use super::{FfaSession, GameSession};
+
+/// A session whose PDA owns a token vault and tracks the balance it should hold
+pub trait SessionVault {
+    const SEED: &'static [u8];
+
+    fn game(&self) -> Pubkey;
+    fn seed(&self) -> u64;
+    fn bump(&self) -> u8;
+    fn record_withdrawal(&mut self, amount: u64) -> Result<()>;
+}
+
+impl SessionVault for GameSession {
+    const SEED: &'static [u8] = b"game_session";
+
+    fn game(&self) -> Pubkey {
+        self.game
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        GameSession::record_withdrawal(self, amount)
+    }
+}
+
+impl SessionVault for FfaSession {
+    const SEED: &'static [u8] = b"ffa_session";
+
+    fn game(&self) -> Pubkey {
+        self.game
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        FfaSession::record_withdrawal(self, amount)
+    }
+}
+
+/// Transfers tokens out of a session's vault, signed by the session, and records the
+/// withdrawal so `vault_balance` stays in step with every transfer the program makes.
+pub fn transfer_from_vault<'info, T>(
+    session: &mut Account<'info, T>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64
+) -> Result<()>
+where
+    T: SessionVault + AccountSerialize + AccountDeserialize + Owner + Clone
+{
+    let game = session.game();
+    let seed = session.seed().to_le_bytes();
+    let bump = [session.bump()];
+    let signer_seeds: [&[&[u8]]; 1] = [&[T::SEED, game.as_ref(), &seed[..], &bump]];
+
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        to,
+        authority: session.to_account_info(),
+        mint: mint.to_account_info()
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, &signer_seeds);
+
+    transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+    session.record_withdrawal(amount)
+}
+
+/// Closes an emptied session vault, returning its rent to `destination`
+pub fn close_vault<'info, T>(
+    session: &Account<'info, T>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>
+) -> Result<()>
+where
+    T: SessionVault + AccountSerialize + AccountDeserialize + Owner + Clone
+{
+    let game = session.game();
+    let seed = session.seed().to_le_bytes();
+    let bump = [session.bump()];
+    let signer_seeds: [&[&[u8]]; 1] = [&[T::SEED, game.as_ref(), &seed[..], &bump]];
+
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination,
+        authority: session.to_account_info()
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, &signer_seeds);
+
+    close_account(cpi_ctx)
+}
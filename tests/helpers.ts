@@ -21,3 +21,16 @@ export const getGameSessionData = (program, game, GAME_SESSION_SEED, SEED) => {
 
   return program.account.gameSession.fetch(game_session);
 };
+
+export const getFfaSessionData = (program, game, FFA_SESSION_SEED, SEED) => {
+  const ffa_session = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(FFA_SESSION_SEED),
+      game.publicKey.toBuffer(),
+      SEED.toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  )[0];
+
+  return program.account.ffaSession.fetch(ffa_session);
+};
@@ -5,19 +5,29 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { BN } from "bn.js";
 import {
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  ExtensionType,
   getAssociatedTokenAddressSync,
   getMint,
+  getMintLen,
   Mint,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
-import { PublicKey } from "@solana/web3.js";
+import { PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
 import { assert, expect } from "chai";
 import { randomBytes } from "crypto";
 
 import { RefereeProgram } from "../target/types/referee_program";
 
+import { MINT_DECIMALS } from "./constants";
 import { fund } from "./fund";
-import { getConfigData, getGameSessionData } from "./helpers";
+import {
+  getConfigData,
+  getFfaSessionData,
+  getGameSessionData,
+} from "./helpers";
 
 describe("referee_program", () => {
   const provider = anchor.AnchorProvider.env();
@@ -58,6 +68,14 @@ describe("referee_program", () => {
 
   const SEED = new BN(12345678);
 
+  // entry costs are passed in base units of the mint
+  const ONE_TOKEN = new BN(10).pow(new BN(MINT_DECIMALS));
+
+  const JOIN_DEADLINE = new BN(Math.floor(Date.now() / 1000) + 60 * 60);
+  const SETTLEMENT_DEADLINE = new BN(
+    Math.floor(Date.now() / 1000) + 2 * 60 * 60
+  );
+
   const game_a_game_session_1_address = PublicKey.findProgramAddressSync(
     [
       Buffer.from(GAME_SESSION_SEED),
@@ -97,7 +115,7 @@ describe("referee_program", () => {
 
       try {
         await program.methods
-          .initializeProgramConfig(new BN(10001))
+          .initializeProgramConfig(new BN(10001), admin.publicKey, new BN(0))
           .accounts({
             authority: admin.publicKey,
             game: game_a.publicKey,
@@ -120,7 +138,7 @@ describe("referee_program", () => {
 
       expect(
         await program.methods
-          .initializeProgramConfig(FEE)
+          .initializeProgramConfig(FEE, admin.publicKey, new BN(0))
           .accounts({
             authority: admin.publicKey,
             game: game_a.publicKey,
@@ -142,6 +160,10 @@ describe("referee_program", () => {
         config_data.admin.toBase58()
       );
       assert.strictEqual(FEE.toString(), config_data.feeBasisPoints.toString());
+      assert.strictEqual(
+        admin.publicKey.toBase58(),
+        config_data.dustRecipient.toBase58()
+      );
     });
     it("should throw error if program configuration for a game already exists", async () => {
       const mint_a_account = await getMint(
@@ -151,7 +173,7 @@ describe("referee_program", () => {
 
       try {
         await program.methods
-          .initializeProgramConfig(new BN(200))
+          .initializeProgramConfig(new BN(200), admin.publicKey, new BN(0))
           .accounts({
             authority: admin.publicKey,
             game: game_a.publicKey,
@@ -187,7 +209,7 @@ describe("referee_program", () => {
 
       try {
         await program.methods
-          .initializeProgramConfig(FEE)
+          .initializeProgramConfig(FEE, admin.publicKey, new BN(0))
           .accounts({
             authority: player_a.publicKey,
             game: game_b.publicKey,
@@ -210,7 +232,7 @@ describe("referee_program", () => {
 
       expect(
         await program.methods
-          .initializeProgramConfig(FEE)
+          .initializeProgramConfig(FEE, admin.publicKey, new BN(0))
           .accounts({
             authority: admin.publicKey,
             game: game_b.publicKey,
@@ -253,7 +275,7 @@ describe("referee_program", () => {
     it("should throw error if fee basis points are higher than 10000", async () => {
       try {
         await program.methods
-          .updateProgramConfig(new BN(10001))
+          .updateProgramConfig(new BN(10001), admin.publicKey, new BN(0))
           .accounts({
             authority: admin.publicKey,
             game: game_a.publicKey,
@@ -269,7 +291,7 @@ describe("referee_program", () => {
 
       expect(
         await program.methods
-          .updateProgramConfig(FEE)
+          .updateProgramConfig(FEE, admin.publicKey, new BN(0))
           .accounts({
             authority: admin.publicKey,
             game: game_a.publicKey,
@@ -295,7 +317,7 @@ describe("referee_program", () => {
 
       expect(
         await program.methods
-          .updateProgramConfig(FEE)
+          .updateProgramConfig(FEE, admin.publicKey, new BN(0))
           .accounts({
             authority: admin.publicKey,
             game: game_a.publicKey,
@@ -336,7 +358,7 @@ describe("referee_program", () => {
 
       try {
         program.methods
-          .updateProgramConfig(FEE)
+          .updateProgramConfig(FEE, admin.publicKey, new BN(0))
           .accounts({
             authority: player_a.publicKey,
             game: game_a.publicKey,
@@ -354,8 +376,14 @@ describe("referee_program", () => {
         await program.methods
           .initializeSession(
             new BN(1),
-            new BN(100),
-            Array.from({ length: 3 }, () => [])
+            new BN(100).mul(ONE_TOKEN),
+            Array.from({ length: 9 }, () => []),
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
           )
           .accounts({
             game: game_a.publicKey,
@@ -380,7 +408,17 @@ describe("referee_program", () => {
 
       try {
         await program.methods
-          .initializeSession(new BN(1), new BN(100), [team_a, team_b])
+          .initializeSession(
+            new BN(1),
+            new BN(100).mul(ONE_TOKEN),
+            [team_a, team_b],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
           .accounts({
             game: game_a.publicKey,
             mint: created_mint_a_account.address,
@@ -399,7 +437,17 @@ describe("referee_program", () => {
       let team_b = [player_c.publicKey];
       try {
         await program.methods
-          .initializeSession(new BN(1), new BN(100), [team_a, team_b])
+          .initializeSession(
+            new BN(1),
+            new BN(100).mul(ONE_TOKEN),
+            [team_a, team_b],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
           .accounts({
             game: game_a.publicKey,
             mint: created_mint_a_account.address,
@@ -419,7 +467,17 @@ describe("referee_program", () => {
 
       try {
         await program.methods
-          .initializeSession(new BN(1), new BN(100), [team_a, team_b])
+          .initializeSession(
+            new BN(1),
+            new BN(100).mul(ONE_TOKEN),
+            [team_a, team_b],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
           .accounts({
             game: game_a.publicKey,
             mint: created_mint_a_account.address,
@@ -439,7 +497,17 @@ describe("referee_program", () => {
 
       try {
         await program.methods
-          .initializeSession(new BN(1), new BN(100), [team_a, team_b])
+          .initializeSession(
+            new BN(1),
+            new BN(100).mul(ONE_TOKEN),
+            [team_a, team_b],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
           .accounts({
             game: game_a.publicKey,
             mint: created_mint_a_account.address,
@@ -453,11 +521,41 @@ describe("referee_program", () => {
         );
       }
     });
+    it("should throw if the entry cost does not split evenly across a team", async () => {
+      try {
+        await program.methods
+          .initializeSession(
+            new BN(1),
+            new BN(ONE_TOKEN.toNumber() + 1),
+            [
+              [player_a.publicKey, player_b.publicKey],
+              [player_c.publicKey, player_d.publicKey],
+            ],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Cannot initialize, entry cost per team in base units must divide evenly"
+        );
+      }
+    });
     it("should initialize a game session with proper fees", async () => {
       let team_a = [player_a.publicKey, player_b.publicKey];
       let team_b = [player_c.publicKey, player_d.publicKey];
 
-      const SESSION_ENTRY_COST_PER_TEAM = new BN(10);
+      const SESSION_ENTRY_COST_PER_TEAM = new BN(10).mul(ONE_TOKEN);
 
       const game_a_mint_a_ata = await getAssociatedTokenAddressSync(
         created_mint_a_account.address,
@@ -468,10 +566,17 @@ describe("referee_program", () => {
 
       expect(
         await program.methods
-          .initializeSession(SEED, SESSION_ENTRY_COST_PER_TEAM, [
-            team_a,
-            team_b,
-          ])
+          .initializeSession(
+            SEED,
+            SESSION_ENTRY_COST_PER_TEAM,
+            [team_a, team_b],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
           .accountsPartial({
             game: game_a.publicKey,
             mint: created_mint_a_account.address,
@@ -495,27 +600,27 @@ describe("referee_program", () => {
       );
 
       assert.strictEqual(game_a_game_session_1.amountOfTeams, 2);
-      assert.strictEqual(game_a_game_session_1.playersPerTeam, 2);
+      assert.strictEqual(game_a_game_session_1.playersPerTeam[0], 2);
 
       assert.strictEqual(
         game_a_game_session_1.sessionEntryCostPerTeam.toNumber(),
-        SESSION_ENTRY_COST_PER_TEAM.toNumber() *
-          Math.pow(10, created_mint_a_account.decimals)
+        SESSION_ENTRY_COST_PER_TEAM.toNumber()
       );
 
       assert.strictEqual(
-        game_a_game_session_1.sessionEntryCostPerPlayer.toNumber(),
-        (SESSION_ENTRY_COST_PER_TEAM.toNumber() *
-          Math.pow(10, created_mint_a_account.decimals)) /
-          game_a_game_session_1.playersPerTeam
+        game_a_game_session_1.sessionEntryCostPerPlayer[0].toNumber(),
+        SESSION_ENTRY_COST_PER_TEAM.toNumber() /
+          game_a_game_session_1.playersPerTeam[0]
       );
 
       assert.strictEqual(
         game_a_game_session_1.terminationFee.toNumber(),
-        SESSION_ENTRY_COST_PER_TEAM.toNumber() *
-          game_a_game_session_1.amountOfTeams *
-          game_a_program_config.feeBasisPoints.toNumber() *
-          Math.pow(10, created_mint_a_account.decimals - 4) // - 4 for basis points adjustement
+        Math.floor(
+          (SESSION_ENTRY_COST_PER_TEAM.toNumber() *
+            game_a_game_session_1.amountOfTeams *
+            game_a_program_config.feeBasisPoints.toNumber()) /
+            10000
+        )
       );
 
       assert.isTrue(
@@ -585,14 +690,21 @@ describe("referee_program", () => {
       let team_a = [player_a.publicKey, player_b.publicKey];
       let team_b = [player_c.publicKey, player_d.publicKey];
 
-      const SESSION_ENTRY_COST_PER_TEAM = new BN(10);
+      const SESSION_ENTRY_COST_PER_TEAM = new BN(10).mul(ONE_TOKEN);
 
       try {
         await program.methods
-          .initializeSession(SEED, SESSION_ENTRY_COST_PER_TEAM, [
-            team_a,
-            team_b,
-          ])
+          .initializeSession(
+            SEED,
+            SESSION_ENTRY_COST_PER_TEAM,
+            [team_a, team_b],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
           .accountsPartial({
             game: game_a.publicKey,
             mint: created_mint_a_account.address,
@@ -608,16 +720,23 @@ describe("referee_program", () => {
       let team_a = [player_a.publicKey, player_b.publicKey];
       let team_b = [player_c.publicKey, player_d.publicKey];
 
-      const SESSION_ENTRY_COST_PER_TEAM = new BN(100);
+      const SESSION_ENTRY_COST_PER_TEAM = new BN(100).mul(ONE_TOKEN);
 
       const SEED_2 = new BN(randomBytes(8));
 
       expect(
         await program.methods
-          .initializeSession(SEED_2, SESSION_ENTRY_COST_PER_TEAM, [
-            team_a,
-            team_b,
-          ])
+          .initializeSession(
+            SEED_2,
+            SESSION_ENTRY_COST_PER_TEAM,
+            [team_a, team_b],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
           .accountsPartial({
             game: game_a.publicKey,
             mint: created_mint_a_account.address,
@@ -631,7 +750,7 @@ describe("referee_program", () => {
   describe("deposit", () => {
     it("should deposit entry fee for player and set their 'paid' flag to true", async () => {
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_a.publicKey,
           game: game_a.publicKey,
@@ -664,7 +783,7 @@ describe("referee_program", () => {
         session_vault_account.value.amount,
         (
           game_a_game_session_1.terminationFee.toNumber() +
-          game_a_game_session_1.sessionEntryCostPerPlayer.toNumber()
+          game_a_game_session_1.sessionEntryCostPerPlayer[0].toNumber()
         ).toString()
       );
 
@@ -705,7 +824,7 @@ describe("referee_program", () => {
 
       try {
         await program.methods
-          .deposit()
+          .deposit(null, null)
           .accountsPartial({
             player: player_a.publicKey,
             game: game_a.publicKey,
@@ -732,6 +851,45 @@ describe("referee_program", () => {
         player_a_ata_balance_before_tx
       );
 
+      assert.strictEqual(
+        session_vault_account_balance_after_tx,
+        session_vault_account_balance_before_tx
+      );
+    });
+    it("should throw if a player deposits with a mint other than the session's", async () => {
+      let session_vault = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        game_a_game_session_1_address,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      let session_vault_account_balance_before_tx = (
+        await connection.getTokenAccountBalance(session_vault)
+      ).value.amount;
+
+      try {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player_b.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_b_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_1_address,
+            vault: session_vault,
+          })
+          .signers([player_b])
+          .rpc();
+        assert.fail("deposit with the wrong mint should fail");
+      } catch (error) {
+        expect(error.message).to.contain("A has one constraint was violated");
+      }
+
+      let session_vault_account_balance_after_tx = (
+        await connection.getTokenAccountBalance(session_vault)
+      ).value.amount;
+
       assert.strictEqual(
         session_vault_account_balance_after_tx,
         session_vault_account_balance_before_tx
@@ -793,7 +951,7 @@ describe("referee_program", () => {
         player_a_ata_balance_after_tx,
         (
           parseInt(player_a_ata_balance_before_tx) +
-          game_a_game_session_1.sessionEntryCostPerPlayer.toNumber()
+          game_a_game_session_1.sessionEntryCostPerPlayer[0].toNumber()
         ).toString()
       );
 
@@ -801,7 +959,7 @@ describe("referee_program", () => {
         session_vault_account_balance_after_tx,
         (
           parseInt(session_vault_account_balance_before_tx) -
-          game_a_game_session_1.sessionEntryCostPerPlayer.toNumber()
+          game_a_game_session_1.sessionEntryCostPerPlayer[0].toNumber()
         ).toString()
       );
 
@@ -911,7 +1069,7 @@ describe("referee_program", () => {
           .signers([game_a])
           .rpc();
       } catch (error) {
-        expect(error.message).to.contain("Player not eligible for payout");
+        expect(error.message).to.contain("Action not allowed in the current game session status");
       }
 
       let session_vault_account_balance_after_tx = (
@@ -1013,7 +1171,7 @@ describe("referee_program", () => {
           .signers([game_a])
           .rpc();
       } catch (error) {
-        expect(error.message).to.contain("Player not eligible for payout");
+        expect(error.message).to.contain("Action not allowed in the current game session status");
       }
       let session_vault_account_balance_after_tx = (
         await connection.getTokenAccountBalance(session_vault)
@@ -1030,6 +1188,29 @@ describe("referee_program", () => {
         session_vault_account_balance_before_tx
       );
     });
+    it("should throw when starting a session before all players paid", async () => {
+      try {
+        await program.methods
+          .startSession()
+          .accountsPartial({
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_1_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Cannot start, not all players paid");
+      }
+
+      const game_a_game_session_1 = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED
+      );
+
+      assert.deepEqual(game_a_game_session_1.status, { initiated: {} });
+    });
     it("paying entry fee for all players", async () => {
       const game_a_game_session_1 = await getGameSessionData(
         program,
@@ -1049,7 +1230,7 @@ describe("referee_program", () => {
       ).value.amount;
 
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_a.publicKey,
           game: game_a.publicKey,
@@ -1060,7 +1241,7 @@ describe("referee_program", () => {
         .signers([player_a])
         .rpc();
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_b.publicKey,
           game: game_a.publicKey,
@@ -1071,7 +1252,7 @@ describe("referee_program", () => {
         .signers([player_b])
         .rpc();
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_c.publicKey,
           game: game_a.publicKey,
@@ -1082,7 +1263,7 @@ describe("referee_program", () => {
         .signers([player_c])
         .rpc();
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_d.publicKey,
           game: game_a.publicKey,
@@ -1101,10 +1282,166 @@ describe("referee_program", () => {
         session_vault_account_balance_after_tx,
         (
           parseInt(session_vault_account_balance_before_tx) +
-          4 * game_a_game_session_1.sessionEntryCostPerPlayer.toNumber()
+          4 * game_a_game_session_1.sessionEntryCostPerPlayer[0].toNumber()
         ).toString()
       );
     });
+    it("should throw if a player deposits after the session started", async () => {
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_1_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_a_game_session_1 = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED
+      );
+
+      assert.deepEqual(game_a_game_session_1.status, { started: {} });
+
+      try {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_1_address,
+          })
+          .signers([player_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Action not allowed in the current game session status"
+        );
+      }
+    });
+    it("should throw during payout if the session has not ended", async () => {
+      try {
+        await program.methods
+          .payout()
+          .accountsPartial({
+            player: player_b.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_1_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Action not allowed in the current game session status"
+        );
+      }
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_1_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_a_game_session_1 = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED
+      );
+
+      assert.deepEqual(game_a_game_session_1.status, { ended: {} });
+    });
+    it("should declare the winning team", async () => {
+      try {
+        await program.methods
+          .declareResult({ winner: { team: 2 } })
+          .accountsPartial({
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_1_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Winning team does not exist");
+      }
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_1_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_a_game_session_1 = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED
+      );
+
+      assert.deepEqual(game_a_game_session_1.result, { winner: { team: 0 } });
+
+      try {
+        await program.methods
+          .declareResult({ winner: { team: 1 } })
+          .accountsPartial({
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_1_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Result already declared");
+      }
+    });
+    it("should throw during payout if a player is not in the winning team", async () => {
+      let session_vault = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        game_a_game_session_1_address,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      let session_vault_account_balance_before_tx = (
+        await connection.getTokenAccountBalance(session_vault)
+      ).value.amount;
+
+      try {
+        await program.methods
+          .payout()
+          .accountsPartial({
+            player: player_c.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_1_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Player is not in the winning team");
+      }
+
+      let session_vault_account_balance_after_tx = (
+        await connection.getTokenAccountBalance(session_vault)
+      ).value.amount;
+
+      assert.strictEqual(
+        session_vault_account_balance_after_tx,
+        session_vault_account_balance_before_tx
+      );
+    });
     it("should payout winnigs to a player that paid entry fee", async () => {
       let session_vault = getAssociatedTokenAddressSync(
         created_mint_a_account.address,
@@ -1157,7 +1494,7 @@ describe("referee_program", () => {
       const winnigs =
         (parseInt(session_vault_account_balance_before_tx) -
           2 * game_a_game_session_1.terminationFee.toNumber()) /
-        game_a_game_session_1.playersPerTeam;
+        game_a_game_session_1.playersPerTeam[0];
 
       assert.strictEqual(
         player_b_ata_balance_after_tx,
@@ -1214,7 +1551,7 @@ describe("referee_program", () => {
           .signers([game_a])
           .rpc();
       } catch (error) {
-        expect(error.message).to.contain("Player not eligible for refund");
+        expect(error.message).to.contain("Action not allowed in the current game session status");
       }
       let session_vault_account_balance_after_tx = (
         await connection.getTokenAccountBalance(session_vault)
@@ -1308,13 +1645,20 @@ describe("referee_program", () => {
       let team_a = [player_a.publicKey];
       let team_b = [player_b.publicKey];
 
-      const SESSION_ENTRY_COST_PER_TEAM = new BN(150);
+      const SESSION_ENTRY_COST_PER_TEAM = new BN(150).mul(ONE_TOKEN);
 
       await program.methods
-        .initializeSession(SEED_1, SESSION_ENTRY_COST_PER_TEAM, [
-          team_a,
-          team_b,
-        ])
+        .initializeSession(
+          SEED_1,
+          SESSION_ENTRY_COST_PER_TEAM,
+          [team_a, team_b],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
         .accountsPartial({
           game: game_a.publicKey,
           mint: created_mint_a_account.address,
@@ -1324,7 +1668,7 @@ describe("referee_program", () => {
         .rpc();
 
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_a.publicKey,
           game: game_a.publicKey,
@@ -1336,7 +1680,7 @@ describe("referee_program", () => {
         .rpc();
 
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_b.publicKey,
           game: game_a.publicKey,
@@ -1434,7 +1778,7 @@ describe("referee_program", () => {
         session_vault_account_balance_after_tx,
         (
           parseInt(session_vault_account_balance_before_tx) -
-          2 * game_session.sessionEntryCostPerPlayer.toNumber()
+          2 * game_session.sessionEntryCostPerPlayer[0].toNumber()
         ).toString()
       );
     });
@@ -1501,11 +1845,11 @@ describe("referee_program", () => {
       let team_a = [player_a.publicKey, player_c.publicKey];
       let team_b = [player_b.publicKey, player_d.publicKey];
 
-      const SESSION_ENTRY_COST_PER_TEAM = new BN(100);
+      const SESSION_ENTRY_COST_PER_TEAM = new BN(100).mul(ONE_TOKEN);
 
       expect(
         await program.methods
-          .updateProgramConfig(new BN(200))
+          .updateProgramConfig(new BN(200), admin.publicKey, new BN(0))
           .accounts({
             authority: admin.publicKey,
             game: game_a.publicKey,
@@ -1515,10 +1859,17 @@ describe("referee_program", () => {
       ).to.not.throw;
 
       await program.methods
-        .initializeSession(SEED_2, SESSION_ENTRY_COST_PER_TEAM, [
-          team_a,
-          team_b,
-        ])
+        .initializeSession(
+          SEED_2,
+          SESSION_ENTRY_COST_PER_TEAM,
+          [team_a, team_b],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
         .accountsPartial({
           game: game_a.publicKey,
           mint: created_mint_a_account.address,
@@ -1528,7 +1879,7 @@ describe("referee_program", () => {
         .rpc();
 
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_a.publicKey,
           game: game_a.publicKey,
@@ -1540,7 +1891,7 @@ describe("referee_program", () => {
         .rpc();
 
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_b.publicKey,
           game: game_a.publicKey,
@@ -1552,7 +1903,7 @@ describe("referee_program", () => {
         .rpc();
 
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_c.publicKey,
           game: game_a.publicKey,
@@ -1564,7 +1915,7 @@ describe("referee_program", () => {
         .rpc();
 
       await program.methods
-        .deposit()
+        .deposit(null, null)
         .accountsPartial({
           player: player_d.publicKey,
           game: game_a.publicKey,
@@ -1574,28 +1925,55 @@ describe("referee_program", () => {
         })
         .signers([player_d])
         .rpc();
-    });
-    it("should throw if not all eligible players recieved winnings", async () => {
+
       await program.methods
-        .payout()
+        .startSession()
         .accountsPartial({
-          player: player_a.publicKey,
           game: game_a.publicKey,
-          mint: created_mint_a_account.address,
-          tokenProgram: TOKEN_PROGRAM_ID,
           gameSession: game_a_game_session_33334444_address,
         })
         .signers([game_a])
         .rpc();
 
-      let session_vault = getAssociatedTokenAddressSync(
-        created_mint_a_account.address,
-        game_a_game_session_33334444_address,
-        true,
-        TOKEN_PROGRAM_ID
-      );
-
-      let session_vault_account_balance_before_tx = (
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_33334444_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_33334444_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if not all eligible players recieved winnings", async () => {
+      await program.methods
+        .payout()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_33334444_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      let session_vault = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        game_a_game_session_33334444_address,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      let session_vault_account_balance_before_tx = (
         await connection.getTokenAccountBalance(session_vault)
       ).value.amount;
 
@@ -1701,4 +2079,5455 @@ describe("referee_program", () => {
       );
     });
   });
+  describe("draw", () => {
+    const SEED_DRAW = new BN(55556666);
+
+    const game_a_game_session_55556666_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_DRAW.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    it("create game session and declare a draw", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_DRAW,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_b.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_55556666_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_55556666_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_55556666_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ draw: {} })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_55556666_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_DRAW
+      );
+
+      assert.deepEqual(game_session.result, { draw: {} });
+    });
+    it("should split the prize pool across every player after a draw", async () => {
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_DRAW
+      );
+
+      const winnings =
+        (game_session.sessionEntryCostPerTeam.toNumber() *
+          game_session.amountOfTeams -
+          game_session.terminationFee.toNumber()) /
+        (game_session.amountOfTeams * game_session.playersPerTeam[0]);
+
+      for (const player of [player_a, player_b]) {
+        const player_ata = getAssociatedTokenAddressSync(
+          created_mint_a_account.address,
+          player.publicKey,
+          true,
+          TOKEN_PROGRAM_ID
+        );
+
+        const player_ata_balance_before_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        await program.methods
+          .payout()
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_55556666_address,
+          })
+          .signers([game_a])
+          .rpc();
+
+        const player_ata_balance_after_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        assert.strictEqual(
+          player_ata_balance_after_tx,
+          (parseInt(player_ata_balance_before_tx) + winnings).toString()
+        );
+      }
+    });
+    it("should close the game session after a draw and return the termination fee deposit", async () => {
+      const game_ata = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        game_a.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      const game_ata_balance_before_tx = (
+        await connection.getTokenAccountBalance(game_ata)
+      ).value.amount;
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_DRAW
+      );
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_55556666_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_ata_balance_after_tx = (
+        await connection.getTokenAccountBalance(game_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        game_ata_balance_after_tx,
+        (
+          parseInt(game_ata_balance_before_tx) +
+          game_session.terminationFee.toNumber()
+        ).toString()
+      );
+    });
+  });
+  describe("claim_refund", () => {
+    const SEED_EXPIRED = new BN(77778888);
+
+    const game_a_game_session_77778888_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_EXPIRED.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    it("should throw if a player claims a refund before the join deadline", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_EXPIRED,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_b.publicKey]],
+          new BN(Math.floor(Date.now() / 1000) + 10),
+          new BN(Math.floor(Date.now() / 1000) + 20),
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .deposit(null, null)
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_77778888_address,
+        })
+        .signers([player_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .claimRefund()
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_77778888_address,
+          })
+          .signers([player_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Refund cannot be claimed yet");
+      }
+    });
+    it("should throw if a player deposits after the join deadline", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 15000));
+
+      try {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player_b.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_77778888_address,
+          })
+          .signers([player_b])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Join deadline has passed");
+      }
+    });
+    it("should let a paid player claim their own refund after the join deadline", async () => {
+      const player_a_ata = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player_a.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      const player_a_ata_balance_before_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      await program.methods
+        .claimRefund()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_77778888_address,
+        })
+        .signers([player_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_EXPIRED
+      );
+
+      const player_a_ata_balance_after_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        player_a_ata_balance_after_tx,
+        (
+          parseInt(player_a_ata_balance_before_tx) +
+          game_session.sessionEntryCostPerPlayer[0].toNumber()
+        ).toString()
+      );
+    });
+    it("should close a session that never filled up once paid players are refunded", async () => {
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_77778888_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session_account = await connection.getAccountInfo(
+        game_a_game_session_77778888_address
+      );
+
+      assert.isNull(game_session_account);
+    });
+  });
+  describe("settlement deadline", () => {
+    const SEED_UNSETTLED = new BN(99990000);
+    const SEED_UNPAID = new BN(99991111);
+
+    const game_a_game_session_99990000_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_UNSETTLED.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const game_a_game_session_99991111_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_UNPAID.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    it("create and start game session", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_UNSETTLED,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_b.publicKey]],
+          new BN(Math.floor(Date.now() / 1000) + 10),
+          new BN(Math.floor(Date.now() / 1000) + 20),
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_99990000_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_99990000_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("create a session whose declared winnings are never paid out", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_UNPAID,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_c.publicKey], [player_d.publicKey]],
+          new BN(Math.floor(Date.now() / 1000) + 10),
+          new BN(Math.floor(Date.now() / 1000) + 20),
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_c, player_d]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_99991111_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_99991111_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_99991111_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_99991111_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .claimWinnings()
+          .accountsPartial({
+            player: player_c.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_99991111_address,
+          })
+          .signers([player_c])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Winnings cannot be claimed before the settlement deadline"
+        );
+      }
+    });
+    it("should throw if a result is declared after the settlement deadline", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 25000));
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_99990000_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .declareResult({ winner: { team: 0 } })
+          .accountsPartial({
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_99990000_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Settlement deadline has passed");
+      }
+    });
+    it("should let every paid player reclaim their entry after the settlement deadline", async () => {
+      for (const player of [player_a, player_b]) {
+        await program.methods
+          .claimRefund()
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_99990000_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_UNSETTLED
+      );
+
+      assert.isTrue(
+        game_session.teams
+          .slice(0, game_session.amountOfTeams)
+          .every((team) =>
+            team
+              .slice(0, game_session.playersPerTeam[0])
+              .every((player) => player.refunded)
+          )
+      );
+    });
+    it("should forfeit the termination fee deposit to the protocol on close", async () => {
+      const admin_ata = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        admin.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      const admin_ata_balance_before_tx = (
+        await connection.getTokenAccountBalance(admin_ata)
+      ).value.amount;
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_UNSETTLED
+      );
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_99990000_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const admin_ata_balance_after_tx = (
+        await connection.getTokenAccountBalance(admin_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        admin_ata_balance_after_tx,
+        (
+          parseInt(admin_ata_balance_before_tx) +
+          game_session.terminationFee.toNumber()
+        ).toString()
+      );
+    });
+    it("should let a winner claim declared winnings the game never paid out", async () => {
+      const player_c_ata = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player_c.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      // a declared result is not refundable, the winnings are owed instead
+      try {
+        await program.methods
+          .claimRefund()
+          .accountsPartial({
+            player: player_d.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_99991111_address,
+          })
+          .signers([player_d])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Refund cannot be claimed yet");
+      }
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_UNPAID
+      );
+
+      const winnings = game_session.sessionEntryCostPerTeam
+        .muln(game_session.amountOfTeams)
+        .sub(game_session.terminationFee);
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_c_ata)
+      ).value.amount;
+
+      await program.methods
+        .claimWinnings()
+        .accountsPartial({
+          player: player_c.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_99991111_address,
+        })
+        .signers([player_c])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_c_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(winnings).toString()
+      );
+
+      // with every winner paid the session settles as usual
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_99991111_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+  });
+  describe("cancel_session", () => {
+    const SEED_CANCEL = new BN(10102020);
+
+    const game_a_game_session_10102020_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_CANCEL.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const player_atas = [player_a, player_b, player_c].map((player) => ({
+      pubkey: getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      ),
+      isSigner: false,
+      isWritable: true,
+    }));
+
+    it("create game session", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_CANCEL,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey, player_b.publicKey], [player_c.publicKey, player_d.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_10102020_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+    });
+    it("should throw if player accounts do not match the paid players", async () => {
+      try {
+        await program.methods
+          .cancelSession()
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_10102020_address,
+          })
+          .remainingAccounts(player_atas.slice(0, 2))
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Player accounts do not match the eligible players"
+        );
+      }
+    });
+    it("should refund every paid player and cancel the session", async () => {
+      let session_vault = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        game_a_game_session_10102020_address,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      await program.methods
+        .cancelSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_10102020_address,
+        })
+        .remainingAccounts(player_atas)
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_CANCEL
+      );
+
+      assert.deepEqual(game_session.status, { cancelled: {} });
+
+      let session_vault_account = await connection.getTokenAccountBalance(
+        session_vault
+      );
+
+      assert.strictEqual(
+        session_vault_account.value.amount,
+        game_session.terminationFee.toString()
+      );
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_10102020_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+  });
+  describe("payout_team", () => {
+    const SEED_TEAM_PAYOUT = new BN(30304040);
+    const SEED_TEAM_PAYOUT_REFUNDED = new BN(30305050);
+    const SEED_LAST_MEMBER_REFUND = new BN(30306060);
+    const SEED_FEE_REFUND = new BN(30307070);
+
+    const [
+      game_a_game_session_30304040_address,
+      game_a_game_session_30305050_address,
+      game_a_game_session_30306060_address,
+      game_a_game_session_30307070_address,
+    ] = [
+      SEED_TEAM_PAYOUT,
+      SEED_TEAM_PAYOUT_REFUNDED,
+      SEED_LAST_MEMBER_REFUND,
+      SEED_FEE_REFUND,
+    ].map(
+      (seed) =>
+        PublicKey.findProgramAddressSync(
+          [
+            Buffer.from(GAME_SESSION_SEED),
+            game_a.publicKey.toBuffer(),
+            seed.toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        )[0]
+    );
+
+    const [player_c_ata, player_d_ata] = [player_c, player_d].map((player) =>
+      getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      )
+    );
+
+    it("create, play and settle game session", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_TEAM_PAYOUT,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey, player_b.publicKey], [player_c.publicKey, player_d.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c, player_d]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_30304040_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_30304040_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_30304040_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 1 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_30304040_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if the team did not win", async () => {
+      try {
+        await program.methods
+          .payoutTeam(0)
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_30304040_address,
+          })
+          .remainingAccounts(
+            [player_c_ata, player_d_ata].map((pubkey) => ({
+              pubkey,
+              isSigner: false,
+              isWritable: true,
+            }))
+          )
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Team is not a winning team");
+      }
+    });
+    it("should pay every member of the winning team in one transaction", async () => {
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_TEAM_PAYOUT
+      );
+
+      const winnings =
+        (game_session.sessionEntryCostPerTeam.toNumber() *
+          game_session.amountOfTeams -
+          game_session.terminationFee.toNumber()) /
+        game_session.playersPerTeam[0];
+
+      const balances_before_tx = await Promise.all(
+        [player_c_ata, player_d_ata].map(
+          async (ata) =>
+            (await connection.getTokenAccountBalance(ata)).value.amount
+        )
+      );
+
+      await program.methods
+        .payoutTeam(1)
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_30304040_address,
+        })
+        .remainingAccounts(
+          [player_c_ata, player_d_ata].map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([game_a])
+        .rpc();
+
+      const balances_after_tx = await Promise.all(
+        [player_c_ata, player_d_ata].map(
+          async (ata) =>
+            (await connection.getTokenAccountBalance(ata)).value.amount
+        )
+      );
+
+      balances_after_tx.forEach((balance, i) =>
+        assert.strictEqual(
+          balance,
+          (parseInt(balances_before_tx[i]) + winnings).toString()
+        )
+      );
+
+      const settled_game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_TEAM_PAYOUT
+      );
+
+      assert.isTrue(
+        settled_game_session.teams[1]
+          .slice(0, settled_game_session.playersPerTeam[0])
+          .every((player) => player.recievedRewards)
+      );
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_30304040_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should close once the winners are paid if a winner was refunded mid-game", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_TEAM_PAYOUT_REFUNDED,
+          new BN(100).mul(ONE_TOKEN),
+          [
+            [player_a.publicKey, player_b.publicKey],
+            [player_c.publicKey, player_d.publicKey],
+          ],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c, player_d]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_30305050_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_30305050_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      // player_d drops out of the winning team while the game is running
+      await program.methods
+        .refund()
+        .accountsPartial({
+          player: player_d.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_30305050_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_30305050_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 1 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_30305050_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .payoutTeam(1)
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_30305050_address,
+        })
+        .remainingAccounts([
+          { pubkey: player_c_ata, isSigner: false, isWritable: true },
+        ])
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_30305050_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      assert.isNull(
+        await connection.getAccountInfo(game_a_game_session_30305050_address)
+      );
+    });
+    it("should throw if a running session's refund would empty a team", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_LAST_MEMBER_REFUND,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_b.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_30306060_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_30306060_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .refund()
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_30306060_address,
+          })
+          .signers([game_a])
+          .rpc();
+        assert.fail("refunding the last member of a team should fail");
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Cannot refund the last staked player of a team while the session is running"
+        );
+      }
+    });
+    it("should throw if a running session's refund leaves less staked than the fee", async () => {
+      // a minimum fee close to the whole pot leaves no room for a refund
+      await program.methods
+        .updateFeeSchedule(new BN(350).mul(ONE_TOKEN), new BN(0), [])
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      await program.methods
+        .initializeSession(
+          SEED_FEE_REFUND,
+          new BN(200).mul(ONE_TOKEN),
+          [
+            [player_a.publicKey, player_b.publicKey],
+            [player_c.publicKey, player_d.publicKey],
+          ],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .updateFeeSchedule(new BN(0), new BN(0), [])
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c, player_d]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_30307070_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_30307070_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .refund()
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_30307070_address,
+          })
+          .signers([game_a])
+          .rpc();
+        assert.fail("refunding below the fee should fail");
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Refund would leave less staked than the fee and jackpot contribution"
+        );
+      }
+    });
+  });
+  describe("open seats", () => {
+    const SEED_OPEN = new BN(50506060);
+    const SEED_ASSIGNED = new BN(70708080);
+
+    const [
+      game_a_game_session_50506060_address,
+      game_a_game_session_70708080_address,
+    ] = [SEED_OPEN, SEED_ASSIGNED].map(
+      (seed) =>
+        PublicKey.findProgramAddressSync(
+          [
+            Buffer.from(GAME_SESSION_SEED),
+            game_a.publicKey.toBuffer(),
+            seed.toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        )[0]
+    );
+
+    it("should create game sessions with open seats", async () => {
+      for (const [seed, game_assigns_seats] of [
+        [SEED_OPEN, false],
+        [SEED_ASSIGNED, true],
+      ] as [BN, boolean][]) {
+        await program.methods
+          .initializeSession(
+            seed,
+            new BN(100).mul(ONE_TOKEN),
+            [[player_a.publicKey, PublicKey.default], [player_c.publicKey, PublicKey.default]],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            game_assigns_seats,
+            [10000],
+            false,
+            false
+          )
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([game_a])
+          .rpc();
+      }
+    });
+    it("should seat a player in an open seat on deposit", async () => {
+      await program.methods
+        .joinOpenSeat(0, 1, null, null)
+        .accountsPartial({
+          player: player_b.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_50506060_address,
+        })
+        .signers([player_b])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_OPEN
+      );
+
+      assert.strictEqual(
+        game_session.teams[0][1].player.toBase58(),
+        player_b.publicKey.toBase58()
+      );
+      assert.strictEqual(game_session.teams[0][1].paid, true);
+    });
+    it("should throw if a seated player joins another open seat", async () => {
+      try {
+        await program.methods
+          .joinOpenSeat(1, 1, null, null)
+          .accountsPartial({
+            player: player_b.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_50506060_address,
+          })
+          .signers([player_b])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Player already seated");
+      }
+    });
+    it("should throw if the seat is already taken", async () => {
+      try {
+        await program.methods
+          .joinOpenSeat(0, 1, null, null)
+          .accountsPartial({
+            player: player_d.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_50506060_address,
+          })
+          .signers([player_d])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Seat is not open");
+      }
+    });
+    it("should require the game to co-sign seat assignment when configured", async () => {
+      try {
+        await program.methods
+          .joinOpenSeat(1, 1, null, null)
+          .accountsPartial({
+            player: player_d.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_70708080_address,
+          })
+          .signers([player_d])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Game must co-sign the seat assignment"
+        );
+      }
+
+      await program.methods
+        .joinOpenSeat(1, 1, null, null)
+        .accountsPartial({
+          player: player_d.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_70708080_address,
+        })
+        // `game` is not a signer in the IDL, mark it as one for the co-signature
+        .remainingAccounts([
+          { pubkey: game_a.publicKey, isSigner: true, isWritable: false },
+        ])
+        .signers([player_d, game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_ASSIGNED
+      );
+
+      assert.strictEqual(
+        game_session.teams[1][1].player.toBase58(),
+        player_d.publicKey.toBase58()
+      );
+    });
+  });
+  describe("leave_session", () => {
+    const SEED_OPEN = new BN(50506060);
+
+    const game_a_game_session_50506060_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_OPEN.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    it("should throw if a player that didn't pay leaves", async () => {
+      try {
+        await program.methods
+          .leaveSession()
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_50506060_address,
+          })
+          .signers([player_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Player not eligible for refund");
+      }
+    });
+    it("should refund a leaving player and free their seat", async () => {
+      const player_b_ata = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player_b.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      const player_b_ata_balance_before_tx = (
+        await connection.getTokenAccountBalance(player_b_ata)
+      ).value.amount;
+
+      await program.methods
+        .leaveSession()
+        .accountsPartial({
+          player: player_b.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_50506060_address,
+        })
+        .signers([player_b])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_OPEN
+      );
+
+      const player_b_ata_balance_after_tx = (
+        await connection.getTokenAccountBalance(player_b_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        player_b_ata_balance_after_tx,
+        (
+          parseInt(player_b_ata_balance_before_tx) +
+          game_session.sessionEntryCostPerPlayer[0].toNumber()
+        ).toString()
+      );
+
+      assert.strictEqual(
+        game_session.teams[0][1].player.toBase58(),
+        PublicKey.default.toBase58()
+      );
+      assert.strictEqual(game_session.teams[0][1].paid, false);
+      assert.strictEqual(game_session.teams[0][1].refunded, false);
+    });
+    it("should let a player that left join again", async () => {
+      await program.methods
+        .joinOpenSeat(0, 1, null, null)
+        .accountsPartial({
+          player: player_b.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_50506060_address,
+        })
+        .signers([player_b])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_OPEN
+      );
+
+      assert.strictEqual(
+        game_session.teams[0][1].player.toBase58(),
+        player_b.publicKey.toBase58()
+      );
+      assert.strictEqual(game_session.teams[0][1].paid, true);
+    });
+  });
+  describe("replace_player", () => {
+    const SEED_OPEN = new BN(50506060);
+
+    const game_a_game_session_50506060_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_OPEN.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    it("should throw if the new player is already seated", async () => {
+      try {
+        await program.methods
+          .replacePlayer(0, 0, player_c.publicKey)
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_50506060_address,
+            playerAta: null,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Cannot replace, players not unique");
+      }
+    });
+    it("should throw if a paid seat is replaced without the player account", async () => {
+      try {
+        await program.methods
+          .replacePlayer(0, 1, player_d.publicKey)
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_50506060_address,
+            playerAta: null,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Player account required to refund a paid seat"
+        );
+      }
+    });
+    it("should refund the replaced player and seat the new one unpaid", async () => {
+      const player_b_ata = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player_b.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      const player_b_ata_balance_before_tx = (
+        await connection.getTokenAccountBalance(player_b_ata)
+      ).value.amount;
+
+      await program.methods
+        .replacePlayer(0, 1, player_d.publicKey)
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_50506060_address,
+          playerAta: player_b_ata,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_OPEN
+      );
+
+      const player_b_ata_balance_after_tx = (
+        await connection.getTokenAccountBalance(player_b_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        player_b_ata_balance_after_tx,
+        (
+          parseInt(player_b_ata_balance_before_tx) +
+          game_session.sessionEntryCostPerPlayer[0].toNumber()
+        ).toString()
+      );
+
+      assert.strictEqual(
+        game_session.teams[0][1].player.toBase58(),
+        player_d.publicKey.toBase58()
+      );
+      assert.strictEqual(game_session.teams[0][1].paid, false);
+    });
+  });
+  describe("forfeit", () => {
+    const SEED_FORFEIT = new BN(12121212);
+    const SEED_FORFEIT_UNSEATED = new BN(12123434);
+    const SEED_FORFEIT_REFUNDED = new BN(12125656);
+
+    const [
+      game_a_game_session_12121212_address,
+      game_a_game_session_12123434_address,
+      game_a_game_session_12125656_address,
+    ] = [SEED_FORFEIT, SEED_FORFEIT_UNSEATED, SEED_FORFEIT_REFUNDED].map(
+      (seed) =>
+        PublicKey.findProgramAddressSync(
+          [
+            Buffer.from(GAME_SESSION_SEED),
+            game_a.publicKey.toBuffer(),
+            seed.toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        )[0]
+    );
+
+    it("create and start game session with a captain", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_FORFEIT,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey, player_b.publicKey], [player_c.publicKey, player_d.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .designateCaptain(1, player_a.publicKey)
+          .accountsPartial({
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_12121212_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Captain is not a member of the team");
+      }
+
+      await program.methods
+        .designateCaptain(1, player_c.publicKey)
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12121212_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c, player_d]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_12121212_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12121212_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if the signer is not in the forfeiting team", async () => {
+      try {
+        await program.methods
+          .forfeit(1)
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_12121212_address,
+          })
+          .signers([player_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Signer is not a member or captain of the team"
+        );
+      }
+    });
+    it("should only record a member's forfeit until the whole team agrees", async () => {
+      await program.methods
+        .forfeit(0)
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12121212_address,
+        })
+        .signers([player_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_FORFEIT
+      );
+
+      assert.isNull(game_session.result);
+      assert.deepEqual(game_session.status, { started: {} });
+      assert.strictEqual(game_session.forfeitVotes[0], 1);
+    });
+    it("should resolve the session in favour of the other team when the captain forfeits", async () => {
+      await program.methods
+        .forfeit(1)
+        .accountsPartial({
+          player: player_c.publicKey,
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12121212_address,
+        })
+        .signers([player_c])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_FORFEIT
+      );
+
+      assert.deepEqual(game_session.result, {
+        ranked: { placements: [0, 1, 0, 0, 0, 0, 0, 0] },
+      });
+      assert.deepEqual(game_session.status, { ended: {} });
+    });
+    it("should drop the captain once they leave or are replaced", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_FORFEIT_UNSEATED,
+          new BN(100).mul(ONE_TOKEN),
+          [
+            [player_a.publicKey, player_b.publicKey],
+            [player_c.publicKey, player_d.publicKey],
+          ],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .designateCaptain(1, player_d.publicKey)
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12123434_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .deposit(null, null)
+        .accountsPartial({
+          player: player_d.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_12123434_address,
+        })
+        .signers([player_d])
+        .rpc();
+
+      await program.methods
+        .leaveSession()
+        .accountsPartial({
+          player: player_d.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_12123434_address,
+        })
+        .signers([player_d])
+        .rpc();
+
+      let game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_FORFEIT_UNSEATED
+      );
+
+      assert.strictEqual(
+        game_session.captains[1].toBase58(),
+        PublicKey.default.toBase58()
+      );
+
+      await program.methods
+        .designateCaptain(1, player_c.publicKey)
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12123434_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .replacePlayer(1, 0, anchor.web3.Keypair.generate().publicKey)
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_12123434_address,
+          playerAta: null,
+        })
+        .signers([game_a])
+        .rpc();
+
+      game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_FORFEIT_UNSEATED
+      );
+
+      assert.strictEqual(
+        game_session.captains[1].toBase58(),
+        PublicKey.default.toBase58()
+      );
+    });
+    it("should only let members who still have a stake forfeit", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_FORFEIT_REFUNDED,
+          new BN(100).mul(ONE_TOKEN),
+          [
+            [player_a.publicKey, player_b.publicKey],
+            [player_c.publicKey, player_d.publicKey],
+          ],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .designateCaptain(1, player_c.publicKey)
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12125656_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c, player_d]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_12125656_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12125656_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .refund()
+        .accountsPartial({
+          player: player_c.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_12125656_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .forfeit(1)
+          .accountsPartial({
+            player: player_c.publicKey,
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_12125656_address,
+          })
+          .signers([player_c])
+          .rpc();
+        assert.fail("a refunded captain should not forfeit");
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Signer is not a member or captain of the team"
+        );
+      }
+
+      // the refunded seat has no vote, so the last staked member decides
+      await program.methods
+        .forfeit(1)
+        .accountsPartial({
+          player: player_d.publicKey,
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_12125656_address,
+        })
+        .signers([player_d])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_FORFEIT_REFUNDED
+      );
+
+      assert.deepEqual(game_session.result, {
+        ranked: { placements: [0, 1, 0, 0, 0, 0, 0, 0] },
+      });
+    });
+  });
+  describe("ranked payouts", () => {
+    const SEED_RANKED = new BN(13131313);
+
+    const game_a_game_session_13131313_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_RANKED.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const PAYOUT_TABLE = [6000, 2500, 1500];
+
+    it("should throw if the payout table does not add up", async () => {
+      try {
+        await program.methods
+          .initializeSession(
+            SEED_RANKED,
+            new BN(100).mul(ONE_TOKEN),
+            [[player_a.publicKey], [player_b.publicKey], [player_c.publicKey]],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [6000, 2500],
+            false,
+            false
+          )
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Cannot initialize, payout table must split 10000 basis points"
+        );
+      }
+    });
+    it("create and play a three team game session", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_RANKED,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_b.publicKey], [player_c.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          PAYOUT_TABLE,
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_13131313_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_13131313_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_13131313_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if a single winner is declared", async () => {
+      try {
+        await program.methods
+          .declareResult({ winner: { team: 0 } })
+          .accountsPartial({
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_13131313_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Payout table is not winner takes all, placements required"
+        );
+      }
+    });
+    it("should throw if a team is ranked twice", async () => {
+      try {
+        await program.methods
+          .declareResult({ ranked: { placements: [0, 0, 1, 0, 0, 0, 0, 0] } })
+          .accountsPartial({
+            game: game_a.publicKey,
+            gameSession: game_a_game_session_13131313_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Placements must rank each team or entrant exactly once"
+        );
+      }
+    });
+    it("should declare the placements", async () => {
+      await program.methods
+        .declareResult({ ranked: { placements: [2, 0, 1, 0, 0, 0, 0, 0] } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_13131313_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_RANKED
+      );
+
+      assert.deepEqual(game_session.result, {
+        ranked: { placements: [2, 0, 1, 0, 0, 0, 0, 0] },
+      });
+    });
+    it("should pay every player their placement's share", async () => {
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_RANKED
+      );
+
+      const prize_pool =
+        game_session.sessionEntryCostPerTeam.toNumber() *
+          game_session.amountOfTeams -
+        game_session.terminationFee.toNumber();
+
+      // team 2 placed first, team 0 second and team 1 third
+      for (const [player, share] of [
+        [player_c, PAYOUT_TABLE[0]],
+        [player_a, PAYOUT_TABLE[1]],
+        [player_b, PAYOUT_TABLE[2]],
+      ] as [anchor.web3.Keypair, number][]) {
+        const player_ata = getAssociatedTokenAddressSync(
+          created_mint_a_account.address,
+          player.publicKey,
+          true,
+          TOKEN_PROGRAM_ID
+        );
+
+        const balance_before_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        await program.methods
+          .payout()
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_13131313_address,
+          })
+          .signers([game_a])
+          .rpc();
+
+        const balance_after_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        assert.strictEqual(
+          balance_after_tx,
+          (
+            parseInt(balance_before_tx) +
+            Math.floor((prize_pool * share) / 10000)
+          ).toString()
+        );
+      }
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_13131313_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+  });
+  describe("free-for-all", () => {
+    const FFA_SESSION_SEED = "ffa_session";
+    const SEED_FFA = new BN(14141414);
+
+    const game_a_ffa_session_14141414_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(FFA_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_FFA.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const PAYOUT_TABLE = [7000, 3000];
+
+    it("should create a free-for-all session", async () => {
+      await program.methods
+        .initializeFfaSession(
+          SEED_FFA,
+          new BN(100).mul(ONE_TOKEN),
+          4,
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          PAYOUT_TABLE
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const ffa_session = await getFfaSessionData(
+        program,
+        game_a,
+        FFA_SESSION_SEED,
+        SEED_FFA
+      );
+
+      assert.strictEqual(ffa_session.maxEntrants, 4);
+      assert.strictEqual(ffa_session.entrants.length, 0);
+      assert.deepEqual(ffa_session.payoutTable, PAYOUT_TABLE);
+      assert.isTrue(ffa_session.terminationFeePaid);
+    });
+    it("should let entrants join until the lobby is full", async () => {
+      for (const player of [player_a, player_b, player_c, player_d]) {
+        await program.methods
+          .joinFfaSession(null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            ffaSession: game_a_ffa_session_14141414_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      const ffa_session = await getFfaSessionData(
+        program,
+        game_a,
+        FFA_SESSION_SEED,
+        SEED_FFA
+      );
+
+      assert.deepEqual(
+        ffa_session.entrants.map((entrant) => entrant.player.toBase58()),
+        [player_a, player_b, player_c, player_d].map((player) =>
+          player.publicKey.toBase58()
+        )
+      );
+      assert.isTrue(ffa_session.entrants.every((entrant) => entrant.paid));
+    });
+    it("should throw if the lobby is full", async () => {
+      try {
+        await program.methods
+          .joinFfaSession(null)
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            ffaSession: game_a_ffa_session_14141414_address,
+          })
+          .signers([player_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Session is full");
+      }
+    });
+    it("play the session and declare the placements", async () => {
+      await program.methods
+        .startFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_14141414_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_14141414_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      // player_d finished first and player_b second
+      await program.methods
+        .declareFfaPlacements(Buffer.from([3, 1]))
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_14141414_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if the entrant is not in a paid placement", async () => {
+      try {
+        await program.methods
+          .payoutFfa()
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            ffaSession: game_a_ffa_session_14141414_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Entrant is not in a paid placement");
+      }
+    });
+    it("should pay the placed entrants their share and close", async () => {
+      const ffa_session = await getFfaSessionData(
+        program,
+        game_a,
+        FFA_SESSION_SEED,
+        SEED_FFA
+      );
+
+      const prize_pool =
+        ffa_session.entryCostPerPlayer.toNumber() * ffa_session.maxEntrants -
+        ffa_session.terminationFee.toNumber();
+
+      for (const [player, share] of [
+        [player_d, PAYOUT_TABLE[0]],
+        [player_b, PAYOUT_TABLE[1]],
+      ] as [anchor.web3.Keypair, number][]) {
+        const player_ata = getAssociatedTokenAddressSync(
+          created_mint_a_account.address,
+          player.publicKey,
+          true,
+          TOKEN_PROGRAM_ID
+        );
+
+        const balance_before_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        await program.methods
+          .payoutFfa()
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            ffaSession: game_a_ffa_session_14141414_address,
+          })
+          .signers([game_a])
+          .rpc();
+
+        const balance_after_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        assert.strictEqual(
+          balance_after_tx,
+          (
+            parseInt(balance_before_tx) +
+            Math.floor((prize_pool * share) / 10000)
+          ).toString()
+        );
+      }
+
+      await program.methods
+        .closeFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          ffaSession: game_a_ffa_session_14141414_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      assert.isNull(
+        await connection.getAccountInfo(game_a_ffa_session_14141414_address)
+      );
+    });
+  });
+  describe("unequal teams", () => {
+    const SEED_UNEQUAL = new BN(15151515);
+
+    const game_a_game_session_15151515_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_UNEQUAL.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    it("should split each team's stake across its own players", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_UNEQUAL,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_b.publicKey, player_c.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          true,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_UNEQUAL
+      );
+
+      const entry_cost_per_team =
+        game_session.sessionEntryCostPerTeam.toNumber();
+
+      assert.deepEqual(game_session.playersPerTeam.slice(0, 2), [1, 2]);
+      assert.strictEqual(
+        game_session.sessionEntryCostPerPlayer[0].toNumber(),
+        entry_cost_per_team
+      );
+      assert.strictEqual(
+        game_session.sessionEntryCostPerPlayer[1].toNumber(),
+        entry_cost_per_team / 2
+      );
+    });
+    it("should pay the winnings per player of the winning team", async () => {
+      for (const player of [player_a, player_b, player_c]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_15151515_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_15151515_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_15151515_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 1 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_15151515_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_UNEQUAL
+      );
+
+      const winnings =
+        (game_session.sessionEntryCostPerTeam.toNumber() *
+          game_session.amountOfTeams -
+          game_session.terminationFee.toNumber()) /
+        game_session.playersPerTeam[1];
+
+      for (const player of [player_b, player_c]) {
+        const player_ata = getAssociatedTokenAddressSync(
+          created_mint_a_account.address,
+          player.publicKey,
+          true,
+          TOKEN_PROGRAM_ID
+        );
+
+        const balance_before_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        await program.methods
+          .payout()
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_15151515_address,
+          })
+          .signers([game_a])
+          .rpc();
+
+        const balance_after_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        assert.strictEqual(
+          balance_after_tx,
+          (parseInt(balance_before_tx) + winnings).toString()
+        );
+      }
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_15151515_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if the mint charges a transfer fee", async () => {
+      const transfer_fee_mint = anchor.web3.Keypair.generate();
+      const mint_len = getMintLen([ExtensionType.TransferFeeConfig]);
+
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.createAccount({
+            fromPubkey: game_a.publicKey,
+            newAccountPubkey: transfer_fee_mint.publicKey,
+            space: mint_len,
+            lamports: await connection.getMinimumBalanceForRentExemption(
+              mint_len
+            ),
+            programId: TOKEN_2022_PROGRAM_ID,
+          }),
+          createInitializeTransferFeeConfigInstruction(
+            transfer_fee_mint.publicKey,
+            game_a.publicKey,
+            game_a.publicKey,
+            100,
+            BigInt(ONE_TOKEN.toString()),
+            TOKEN_2022_PROGRAM_ID
+          ),
+          createInitializeMintInstruction(
+            transfer_fee_mint.publicKey,
+            MINT_DECIMALS,
+            game_a.publicKey,
+            null,
+            TOKEN_2022_PROGRAM_ID
+          )
+        ),
+        [game_a, transfer_fee_mint]
+      );
+
+      try {
+        await program.methods
+          .initializeSession(
+            new BN(15152525),
+            new BN(100).mul(ONE_TOKEN),
+            [[player_a.publicKey], [player_b.publicKey]],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: transfer_fee_mint.publicKey,
+            gameAta: null,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Cannot initialize, mints with a transfer fee are not supported"
+        );
+      }
+    });
+  });
+  describe("variable stakes", () => {
+    const SEED_STAKES = new BN(16161616);
+
+    const game_a_game_session_16161616_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_STAKES.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const [player_a_ata, player_b_ata] = [player_a, player_b].map((player) =>
+      getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      )
+    );
+
+    const STAKE = new BN(150).mul(ONE_TOKEN);
+
+    it("create game session with variable stakes", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_STAKES,
+          new BN(100).mul(ONE_TOKEN),
+          [
+            [player_a.publicKey, player_b.publicKey],
+            [player_c.publicKey, player_d.publicKey],
+          ],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          true
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if the stake is below the entry cost", async () => {
+      try {
+        await program.methods
+          .deposit(new BN(1), null)
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_16161616_address,
+          })
+          .signers([player_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Stake is below the entry cost");
+      }
+    });
+    it("should throw if the stake is paid in a mint other than the session's", async () => {
+      const session_vault = getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        game_a_game_session_16161616_address,
+        true,
+        TOKEN_PROGRAM_ID
+      );
+
+      try {
+        await program.methods
+          .deposit(STAKE, null)
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_b_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_16161616_address,
+            vault: session_vault,
+          })
+          .signers([player_a])
+          .rpc();
+        assert.fail("stake in the wrong mint should fail");
+      } catch (error) {
+        expect(error.message).to.contain("A has one constraint was violated");
+      }
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_STAKES
+      );
+
+      assert.strictEqual(game_session.teams[0][0].paid, false);
+    });
+    it("should refund exactly what the player staked", async () => {
+      await program.methods
+        .deposit(STAKE, null)
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_16161616_address,
+        })
+        .signers([player_a])
+        .rpc();
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      await program.methods
+        .refund()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_16161616_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        (parseInt(balance_before_tx) + STAKE.toNumber()).toString()
+      );
+    });
+    it("should split the winnings in proportion to each member's stake", async () => {
+      for (const [player, stake] of [
+        [player_a, STAKE],
+        [player_b, null],
+        [player_c, null],
+        [player_d, null],
+      ] as [anchor.web3.Keypair, BN | null][]) {
+        await program.methods
+          .deposit(stake, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_16161616_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_16161616_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_16161616_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_16161616_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_STAKES
+      );
+
+      const stakes: BN[] = game_session.teams
+        .flat()
+        .map((player) => player.stake);
+      const total_stake = stakes.reduce(
+        (total, stake) => total.add(stake),
+        new BN(0)
+      );
+      const prize_pool = total_stake.sub(game_session.terminationFee);
+      const team_stake = stakes[0].add(stakes[1]);
+
+      for (const [player, player_ata, stake] of [
+        [player_a, player_a_ata, stakes[0]],
+        [player_b, player_b_ata, stakes[1]],
+      ] as [anchor.web3.Keypair, PublicKey, BN][]) {
+        const balance_before_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        await program.methods
+          .payout()
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_16161616_address,
+          })
+          .signers([game_a])
+          .rpc();
+
+        const balance_after_tx = (
+          await connection.getTokenAccountBalance(player_ata)
+        ).value.amount;
+
+        assert.strictEqual(
+          balance_after_tx,
+          new BN(balance_before_tx)
+            .add(prize_pool.mul(stake).div(team_stake))
+            .toString()
+        );
+      }
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_16161616_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+  });
+  describe("dust", () => {
+    const SEED_DUST = new BN(17171717);
+
+    const game_a_game_session_17171717_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_DUST.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const admin_ata = getAssociatedTokenAddressSync(
+      created_mint_a_account.address,
+      admin.publicKey,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+
+    it("create, play and pay out a session whose shares round down", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_DUST,
+          new BN(100).mul(ONE_TOKEN).add(new BN(1)),
+          [[player_a.publicKey], [player_b.publicKey], [player_c.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [5000, 3000, 2000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_17171717_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_17171717_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_17171717_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ ranked: { placements: [0, 1, 2, 0, 0, 0, 0, 0] } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_17171717_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b, player_c]) {
+        await program.methods
+          .payout()
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_17171717_address,
+          })
+          .signers([game_a])
+          .rpc();
+      }
+    });
+    it("should sweep the dust to the dust recipient on close", async () => {
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_DUST
+      );
+
+      const termination_fee = game_session.terminationFee.toNumber();
+      const dust = game_session.vaultBalance.toNumber() - 2 * termination_fee;
+
+      assert.isAbove(dust, 0);
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(admin_ata)
+      ).value.amount;
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_17171717_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(admin_ata)
+      ).value.amount;
+
+      // the admin receives the protocol fee and, as dust recipient, the dust
+      assert.strictEqual(
+        balance_after_tx,
+        (parseInt(balance_before_tx) + termination_fee + dust).toString()
+      );
+    });
+  });
+  describe("sponsor_deposit", () => {
+    const SEED_SPONSORED = new BN(18181818);
+    const SEED_SPONSORED_CANCEL = new BN(19191919);
+    const SEED_SPONSORED_CAP = new BN(18181919);
+
+    const [
+      game_a_game_session_18181818_address,
+      game_a_game_session_19191919_address,
+      game_a_game_session_18181919_address,
+    ] = [SEED_SPONSORED, SEED_SPONSORED_CANCEL, SEED_SPONSORED_CAP].map(
+      (seed) =>
+        PublicKey.findProgramAddressSync(
+          [
+            Buffer.from(GAME_SESSION_SEED),
+            game_a.publicKey.toBuffer(),
+            seed.toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        )[0]
+    );
+
+    const [player_a_ata, player_c_ata, player_d_ata] = [
+      player_a,
+      player_c,
+      player_d,
+    ].map((player) =>
+      getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      )
+    );
+
+    const sponsorship = (
+      game_session: PublicKey,
+      sponsor: anchor.web3.Keypair
+    ) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("sponsorship"),
+          game_session.toBuffer(),
+          sponsor.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+    // `close` takes the sponsorship, sponsor and sponsor token account of
+    // every open sponsorship ahead of the other remaining accounts
+    const sponsorAccounts = (
+      game_session: PublicKey,
+      sponsor: anchor.web3.Keypair,
+      sponsor_ata: PublicKey
+    ) => [
+      {
+        pubkey: sponsorship(game_session, sponsor),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: sponsor.publicKey, isSigner: false, isWritable: true },
+      { pubkey: sponsor_ata, isSigner: false, isWritable: true },
+    ];
+
+    const BOOST = new BN(100).mul(ONE_TOKEN);
+
+    it("create game sessions", async () => {
+      for (const seed of [
+        SEED_SPONSORED,
+        SEED_SPONSORED_CANCEL,
+        SEED_SPONSORED_CAP,
+      ]) {
+        await program.methods
+          .initializeSession(
+            seed,
+            new BN(100).mul(ONE_TOKEN),
+            [[player_a.publicKey], [player_b.publicKey]],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([game_a])
+          .rpc();
+      }
+    });
+    it("should let any signer top up the prize pool", async () => {
+      for (const player of [player_a, player_b]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_18181818_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      // the second top-up adds to the same sponsorship
+      for (let i = 0; i < 2; i++) {
+        await program.methods
+          .sponsorDeposit(BOOST)
+          .accountsPartial({
+            sponsor: player_c.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_18181818_address,
+          })
+          .signers([player_c])
+          .rpc();
+      }
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_SPONSORED
+      );
+
+      assert.strictEqual(
+        game_session.sponsoredAmount.toString(),
+        BOOST.muln(2).toString()
+      );
+    });
+    it("should pay the sponsored amount out to the winners", async () => {
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_18181818_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_18181818_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_18181818_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_SPONSORED
+      );
+
+      const winnings = game_session.sessionEntryCostPerTeam
+        .muln(game_session.amountOfTeams)
+        .sub(game_session.terminationFee)
+        .add(game_session.sponsoredAmount);
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      await program.methods
+        .payout()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_18181818_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(winnings).toString()
+      );
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_18181818_address,
+        })
+        .remainingAccounts(
+          sponsorAccounts(
+            game_a_game_session_18181818_address,
+            player_c,
+            player_c_ata
+          )
+        )
+        .signers([game_a])
+        .rpc();
+
+      // the sponsorship is closed with the session and its rent returned
+      assert.isNull(
+        await connection.getAccountInfo(
+          sponsorship(game_a_game_session_18181818_address, player_c)
+        )
+      );
+    });
+    it("should throw if a sponsor asks for a refund of a running session", async () => {
+      for (const sponsor of [player_c, player_d]) {
+        await program.methods
+          .sponsorDeposit(BOOST)
+          .accountsPartial({
+            sponsor: sponsor.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_19191919_address,
+          })
+          .signers([sponsor])
+          .rpc();
+      }
+
+      try {
+        await program.methods
+          .refundSponsor()
+          .accountsPartial({
+            sponsor: player_d.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_19191919_address,
+          })
+          .signers([player_d])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Sponsorship cannot be refunded");
+      }
+    });
+    it("should refund the sponsor after the session is cancelled", async () => {
+      await program.methods
+        .cancelSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_19191919_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      let balance_before_tx = (
+        await connection.getTokenAccountBalance(player_c_ata)
+      ).value.amount;
+
+      await program.methods
+        .refundSponsor()
+        .accountsPartial({
+          sponsor: player_c.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_19191919_address,
+        })
+        .signers([player_c])
+        .rpc();
+
+      let balance_after_tx = (
+        await connection.getTokenAccountBalance(player_c_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(BOOST).toString()
+      );
+    });
+    it("should refund the remaining sponsors when the session closes", async () => {
+      try {
+        await program.methods
+          .close()
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_19191919_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Sponsorship accounts do not match the session's sponsors"
+        );
+      }
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_19191919_address,
+        })
+        .remainingAccounts(
+          sponsorAccounts(
+            game_a_game_session_19191919_address,
+            player_d,
+            player_d_ata
+          )
+        )
+        .signers([game_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(BOOST).toString()
+      );
+      assert.isNull(
+        await connection.getAccountInfo(
+          sponsorship(game_a_game_session_19191919_address, player_d)
+        )
+      );
+    });
+    it("should throw if a new sponsorship is below the entry cost of a seat", async () => {
+      try {
+        await program.methods
+          .sponsorDeposit(new BN(1))
+          .accountsPartial({
+            sponsor: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_18181919_address,
+          })
+          .signers([player_a])
+          .rpc();
+        assert.fail("sponsorship below the minimum should fail");
+      } catch (error) {
+        expect(error.message).to.contain(
+          "A new sponsorship must be at least the entry cost of a seat"
+        );
+      }
+    });
+    it("should throw once the session has the maximum number of sponsorships", async () => {
+      for (const sponsor of [player_a, player_b, player_c, player_d]) {
+        await program.methods
+          .sponsorDeposit(BOOST)
+          .accountsPartial({
+            sponsor: sponsor.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_18181919_address,
+          })
+          .signers([sponsor])
+          .rpc();
+      }
+
+      try {
+        await program.methods
+          .sponsorDeposit(BOOST)
+          .accountsPartial({
+            sponsor: admin.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_18181919_address,
+          })
+          .signers([admin])
+          .rpc();
+        assert.fail("a fifth sponsorship should fail");
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Session already has the maximum number of sponsorships"
+        );
+      }
+
+      // existing sponsors can still top up their sponsorship
+      await program.methods
+        .sponsorDeposit(new BN(1))
+        .accountsPartial({
+          sponsor: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_18181919_address,
+        })
+        .signers([player_a])
+        .rpc();
+    });
+  });
+  describe("jackpot", () => {
+    const SEED_JACKPOT_FEED = new BN(20202020);
+    const SEED_JACKPOT_AWARD = new BN(21212121);
+    const SEED_JACKPOT_FFA = new BN(21213131);
+
+    const game_a_ffa_session_21213131_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("ffa_session"),
+          game_a.publicKey.toBuffer(),
+          SEED_JACKPOT_FFA.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const [
+      game_a_game_session_20202020_address,
+      game_a_game_session_21212121_address,
+    ] = [SEED_JACKPOT_FEED, SEED_JACKPOT_AWARD].map(
+      (seed) =>
+        PublicKey.findProgramAddressSync(
+          [
+            Buffer.from(GAME_SESSION_SEED),
+            game_a.publicKey.toBuffer(),
+            seed.toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        )[0]
+    );
+
+    const jackpot_address = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("jackpot"),
+        game_a.publicKey.toBuffer(),
+        created_mint_a_account.address.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+    const jackpot_vault = getAssociatedTokenAddressSync(
+      created_mint_a_account.address,
+      jackpot_address,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+
+    const [player_a_ata, player_b_ata] = [player_a, player_b].map((player) =>
+      getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      )
+    );
+
+    const JACKPOT_FEE = new BN(500);
+
+    // 200 tokens at stake, 5% of it feeds the jackpot
+    const CONTRIBUTION = new BN(10).mul(ONE_TOKEN);
+
+    const playSession = async (address: PublicKey, winner: number) => {
+      for (const player of [player_a, player_b]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({ game: game_a.publicKey, gameSession: address })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({ game: game_a.publicKey, gameSession: address })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: winner } })
+        .accountsPartial({ game: game_a.publicKey, gameSession: address })
+        .signers([game_a])
+        .rpc();
+    };
+
+    it("create the jackpot and game sessions", async () => {
+      await program.methods
+        .updateProgramConfig(new BN(200), admin.publicKey, JACKPOT_FEE)
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      await program.methods
+        .initializeJackpot()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const seed of [SEED_JACKPOT_FEED, SEED_JACKPOT_AWARD]) {
+        await program.methods
+          .initializeSession(
+            seed,
+            new BN(100).mul(ONE_TOKEN),
+            [[player_a.publicKey], [player_b.publicKey]],
+            JOIN_DEADLINE,
+            SETTLEMENT_DEADLINE,
+            false,
+            [10000],
+            false,
+            false
+          )
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([game_a])
+          .rpc();
+      }
+
+      // sessions keep the share they were created with
+      await program.methods
+        .updateProgramConfig(new BN(200), admin.publicKey, new BN(0))
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_JACKPOT_FEED
+      );
+
+      assert.strictEqual(
+        game_session.jackpotBasisPoints.toString(),
+        JACKPOT_FEE.toString()
+      );
+    });
+    it("should throw if the fee and jackpot basis points are too high", async () => {
+      try {
+        await program.methods
+          .updateProgramConfig(new BN(200), admin.publicKey, new BN(9800))
+          .accounts({
+            authority: admin.publicKey,
+            game: game_a.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Fee and jackpot basis points too high"
+        );
+      }
+    });
+    it("should throw instead of overflowing on a huge jackpot share", async () => {
+      try {
+        await program.methods
+          .updateProgramConfig(
+            new BN(200),
+            admin.publicKey,
+            new BN("18446744073709551615")
+          )
+          .accounts({
+            authority: admin.publicKey,
+            game: game_a.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+        assert.fail("a jackpot share overflowing u64 should fail");
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Fee and jackpot basis points too high"
+        );
+      }
+    });
+    it("should feed the jackpot when a settled session is closed", async () => {
+      await playSession(game_a_game_session_20202020_address, 0);
+
+      await program.methods
+        .payout()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_20202020_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .close()
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_20202020_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Jackpot accounts required to pay the session's contribution"
+        );
+      }
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_20202020_address,
+          jackpot: jackpot_address,
+          jackpotVault: jackpot_vault,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const jackpot_balance = (
+        await connection.getTokenAccountBalance(jackpot_vault)
+      ).value.amount;
+
+      assert.strictEqual(jackpot_balance, CONTRIBUTION.toString());
+    });
+    it("should throw if the session has no result", async () => {
+      try {
+        await program.methods
+          .awardJackpot()
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_21212121_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Action not allowed in the current game session status"
+        );
+      }
+    });
+    it("should pay the awarded jackpot out to the winners", async () => {
+      await playSession(game_a_game_session_21212121_address, 1);
+
+      await program.methods
+        .awardJackpot()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_21212121_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_JACKPOT_AWARD
+      );
+
+      assert.strictEqual(
+        game_session.jackpotAwarded.toString(),
+        CONTRIBUTION.toString()
+      );
+
+      const winnings = game_session.sessionEntryCostPerTeam
+        .muln(game_session.amountOfTeams)
+        .sub(game_session.terminationFee)
+        .sub(CONTRIBUTION)
+        .add(game_session.jackpotAwarded);
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_b_ata)
+      ).value.amount;
+
+      await program.methods
+        .payout()
+        .accountsPartial({
+          player: player_b.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_21212121_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_b_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(winnings).toString()
+      );
+
+      // the session still feeds its own share into the emptied jackpot
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_21212121_address,
+          jackpot: jackpot_address,
+          jackpotVault: jackpot_vault,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const jackpot_balance = (
+        await connection.getTokenAccountBalance(jackpot_vault)
+      ).value.amount;
+
+      assert.strictEqual(jackpot_balance, CONTRIBUTION.toString());
+    });
+    it("should leave free-for-all sessions out of the jackpot", async () => {
+      await program.methods
+        .updateProgramConfig(new BN(200), admin.publicKey, JACKPOT_FEE)
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      await program.methods
+        .initializeFfaSession(
+          SEED_JACKPOT_FFA,
+          new BN(100).mul(ONE_TOKEN),
+          2,
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          [10000]
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_b]) {
+        await program.methods
+          .joinFfaSession(null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            ffaSession: game_a_ffa_session_21213131_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_21213131_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_21213131_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareFfaPlacements(Buffer.from([0]))
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_21213131_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const ffa_session = await getFfaSessionData(
+        program,
+        game_a,
+        "ffa_session",
+        SEED_JACKPOT_FFA
+      );
+
+      const jackpot_balance_before_tx = (
+        await connection.getTokenAccountBalance(jackpot_vault)
+      ).value.amount;
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      await program.methods
+        .payoutFfa()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          ffaSession: game_a_ffa_session_21213131_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .closeFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          ffaSession: game_a_ffa_session_21213131_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+      const jackpot_balance_after_tx = (
+        await connection.getTokenAccountBalance(jackpot_vault)
+      ).value.amount;
+
+      // nothing of the pot is set aside for the jackpot
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx)
+          .add(ffa_session.entryCostPerPlayer.muln(2))
+          .sub(ffa_session.terminationFee)
+          .toString()
+      );
+      assert.strictEqual(jackpot_balance_after_tx, jackpot_balance_before_tx);
+
+      await program.methods
+        .updateProgramConfig(new BN(200), admin.publicKey, new BN(0))
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    });
+  });
+  describe("deposit_for_team", () => {
+    const SEED_TEAM_DEPOSIT = new BN(22222222);
+
+    const game_a_game_session_22222222_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_TEAM_DEPOSIT.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const SEED_TEAM_DEPOSIT_EXPIRED = new BN(22223333);
+
+    const game_a_game_session_22223333_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_TEAM_DEPOSIT_EXPIRED.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const [player_b_ata, player_d_ata] = [player_b, player_d].map((player) =>
+      getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      )
+    );
+
+    const SESSION_ENTRY_COST_PER_TEAM = new BN(100).mul(ONE_TOKEN);
+    const SESSION_ENTRY_COST_PER_PLAYER = SESSION_ENTRY_COST_PER_TEAM.divn(2);
+
+    it("create game session", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_TEAM_DEPOSIT,
+          SESSION_ENTRY_COST_PER_TEAM,
+          [
+            [player_a.publicKey, player_c.publicKey],
+            [player_b.publicKey, player_d.publicKey],
+          ],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .deposit(null, null)
+        .accountsPartial({
+          player: player_b.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_22222222_address,
+        })
+        .signers([player_b])
+        .rpc();
+    });
+    it("should throw if a seat of the team is already paid", async () => {
+      try {
+        await program.methods
+          .depositForTeam(1)
+          .accountsPartial({
+            payer: player_d.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_22222222_address,
+          })
+          .signers([player_d])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Every seat of the team must be taken and unpaid"
+        );
+      }
+    });
+    it("should pay every seat of the team from one account", async () => {
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      // player_d pays for the other team, like a team wallet would
+      await program.methods
+        .depositForTeam(0)
+        .accountsPartial({
+          payer: player_d.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_22222222_address,
+        })
+        .signers([player_d])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).sub(SESSION_ENTRY_COST_PER_TEAM).toString()
+      );
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_TEAM_DEPOSIT
+      );
+
+      for (const seat of game_session.teams[0].slice(0, 2)) {
+        assert.isTrue(seat.paid);
+        assert.strictEqual(
+          seat.payer.toString(),
+          player_d.publicKey.toString()
+        );
+        assert.strictEqual(
+          seat.stake.toString(),
+          SESSION_ENTRY_COST_PER_PLAYER.toString()
+        );
+      }
+    });
+    it("should throw if the payer's token account is missing on refund", async () => {
+      try {
+        await program.methods
+          .leaveSession()
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_22222222_address,
+          })
+          .signers([player_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Seat was paid for by another account, its token account is required"
+        );
+      }
+    });
+    it("should refund the payer when a team funded player leaves", async () => {
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      await program.methods
+        .leaveSession()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_22222222_address,
+          payerAta: player_d_ata,
+        })
+        .signers([player_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(SESSION_ENTRY_COST_PER_PLAYER).toString()
+      );
+    });
+    it("should refund the payer when the session is cancelled", async () => {
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      // paid seats in seat order: player_c paid for by player_d, then player_b
+      await program.methods
+        .cancelSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_22222222_address,
+        })
+        .remainingAccounts(
+          [player_d_ata, player_b_ata].map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([game_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(SESSION_ENTRY_COST_PER_PLAYER).toString()
+      );
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_22222222_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should let the payer claim back the seats they paid for once the session expires", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_TEAM_DEPOSIT_EXPIRED,
+          SESSION_ENTRY_COST_PER_TEAM,
+          [
+            [player_a.publicKey, player_b.publicKey],
+            [player_c.publicKey, player_d.publicKey],
+          ],
+          new BN(Math.floor(Date.now() / 1000) + 10),
+          new BN(Math.floor(Date.now() / 1000) + 20),
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .depositForTeam(0)
+        .accountsPartial({
+          payer: player_d.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_22223333_address,
+        })
+        .signers([player_d])
+        .rpc();
+
+      // the other team never pays, so the session expires at the join deadline
+      await new Promise((resolve) => setTimeout(resolve, 15000));
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      await program.methods
+        .claimRefund()
+        .accountsPartial({
+          player: player_d.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_22223333_address,
+        })
+        .signers([player_d])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_d_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(SESSION_ENTRY_COST_PER_TEAM).toString()
+      );
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_TEAM_DEPOSIT_EXPIRED
+      );
+
+      for (const seat of game_session.teams[0].slice(0, 2)) {
+        assert.isTrue(seat.refunded);
+      }
+    });
+  });
+  describe("deposit_for", () => {
+    const SEED_GIFTED = new BN(23232323);
+
+    const game_a_game_session_23232323_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_GIFTED.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const SEED_GIFTED_EXPIRED = new BN(23233434);
+
+    const game_a_game_session_23233434_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_GIFTED_EXPIRED.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const [player_a_ata, player_c_ata] = [player_a, player_c].map((player) =>
+      getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        player.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      )
+    );
+
+    const SESSION_ENTRY_COST_PER_TEAM = new BN(100).mul(ONE_TOKEN);
+
+    it("create game session", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_GIFTED,
+          SESSION_ENTRY_COST_PER_TEAM,
+          [[player_a.publicKey], [player_b.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if the player is not in the session", async () => {
+      try {
+        await program.methods
+          .depositFor(null)
+          .accountsPartial({
+            payer: player_c.publicKey,
+            player: player_d.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_23232323_address,
+          })
+          .signers([player_c])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Player not in game session");
+      }
+    });
+    it("should pay another player's entry", async () => {
+      const [payer_balance_before_tx, player_balance_before_tx] =
+        await Promise.all(
+          [player_c_ata, player_a_ata].map(
+            async (ata) =>
+              (await connection.getTokenAccountBalance(ata)).value.amount
+          )
+        );
+
+      await program.methods
+        .depositFor(null)
+        .accountsPartial({
+          payer: player_c.publicKey,
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_23232323_address,
+        })
+        .signers([player_c])
+        .rpc();
+
+      const [payer_balance_after_tx, player_balance_after_tx] =
+        await Promise.all(
+          [player_c_ata, player_a_ata].map(
+            async (ata) =>
+              (await connection.getTokenAccountBalance(ata)).value.amount
+          )
+        );
+
+      assert.strictEqual(
+        payer_balance_after_tx,
+        new BN(payer_balance_before_tx)
+          .sub(SESSION_ENTRY_COST_PER_TEAM)
+          .toString()
+      );
+      assert.strictEqual(player_balance_after_tx, player_balance_before_tx);
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_GIFTED
+      );
+
+      const seat = game_session.teams[0][0];
+
+      assert.isTrue(seat.paid);
+      assert.strictEqual(seat.payer.toString(), player_c.publicKey.toString());
+    });
+    it("should pay the winnings to the player, not the payer", async () => {
+      await program.methods
+        .deposit(null, null)
+        .accountsPartial({
+          player: player_b.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_23232323_address,
+        })
+        .signers([player_b])
+        .rpc();
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_23232323_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_23232323_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_23232323_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_GIFTED
+      );
+
+      const winnings = SESSION_ENTRY_COST_PER_TEAM.muln(2).sub(
+        game_session.terminationFee
+      );
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      await program.methods
+        .payout()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_23232323_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_a_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(winnings).toString()
+      );
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_23232323_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should let the payer claim back a gifted entry once the session expires", async () => {
+      await program.methods
+        .initializeSession(
+          SEED_GIFTED_EXPIRED,
+          SESSION_ENTRY_COST_PER_TEAM,
+          [[player_a.publicKey], [player_b.publicKey]],
+          new BN(Math.floor(Date.now() / 1000) + 10),
+          new BN(Math.floor(Date.now() / 1000) + 20),
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .depositFor(null)
+        .accountsPartial({
+          payer: player_c.publicKey,
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_23233434_address,
+        })
+        .signers([player_c])
+        .rpc();
+
+      // player_b never pays, so the session expires at the join deadline
+      await new Promise((resolve) => setTimeout(resolve, 15000));
+
+      const balance_before_tx = (
+        await connection.getTokenAccountBalance(player_c_ata)
+      ).value.amount;
+
+      await program.methods
+        .claimRefund()
+        .accountsPartial({
+          player: player_c.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_23233434_address,
+        })
+        .signers([player_c])
+        .rpc();
+
+      const balance_after_tx = (
+        await connection.getTokenAccountBalance(player_c_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        balance_after_tx,
+        new BN(balance_before_tx).add(SESSION_ENTRY_COST_PER_TEAM).toString()
+      );
+
+      // the seat was refunded once, the player has nothing left to claim
+      try {
+        await program.methods
+          .claimRefund()
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_23233434_address,
+            payerAta: player_c_ata,
+          })
+          .signers([player_a])
+          .rpc();
+        assert.fail("a refunded seat should not be claimed again");
+      } catch (error) {
+        expect(error.message).to.contain("Player not eligible for refund");
+      }
+    });
+  });
+  describe("fee recipients", () => {
+    const SEED_FEE_SPLIT = new BN(24242424);
+
+    const game_a_game_session_24242424_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_FEE_SPLIT.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const [admin_ata, player_b_ata, player_c_ata] = [
+      admin,
+      player_b,
+      player_c,
+    ].map((owner) =>
+      getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        owner.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      )
+    );
+
+    const recipient_accounts = [player_b_ata, player_c_ata].map((pubkey) => ({
+      pubkey,
+      isSigner: false,
+      isWritable: true,
+    }));
+
+    it("should throw if the shares add up to more than the fee", async () => {
+      try {
+        await program.methods
+          .setFeeRecipients([
+            { recipient: player_b.publicKey, basisPoints: 6000 },
+            { recipient: player_c.publicKey, basisPoints: 5000 },
+          ])
+          .accounts({
+            authority: admin.publicKey,
+            game: game_a.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Fee recipients must be unique with shares adding up to at most 10000 basis points"
+        );
+      }
+    });
+    it("set fee recipients and play a session", async () => {
+      await program.methods
+        .setFeeRecipients([
+          { recipient: player_b.publicKey, basisPoints: 5000 },
+          { recipient: player_c.publicKey, basisPoints: 2500 },
+        ])
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      await program.methods
+        .initializeSession(
+          SEED_FEE_SPLIT,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_d.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const player of [player_a, player_d]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_24242424_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_24242424_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_24242424_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_24242424_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .payout()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_24242424_address,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if the fee recipients' accounts are missing", async () => {
+      try {
+        await program.methods
+          .close()
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_24242424_address,
+          })
+          .signers([game_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Fee recipient accounts do not match the program config"
+        );
+      }
+    });
+    it("should split the fee between the recipients and the protocol", async () => {
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_FEE_SPLIT
+      );
+
+      const termination_fee = game_session.terminationFee;
+      const shares = [termination_fee.divn(2), termination_fee.divn(4)];
+      const protocol_share = termination_fee.sub(shares[0]).sub(shares[1]);
+
+      const balances_before_tx = await Promise.all(
+        [player_b_ata, player_c_ata, admin_ata].map(
+          async (ata) =>
+            (await connection.getTokenAccountBalance(ata)).value.amount
+        )
+      );
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_24242424_address,
+        })
+        .remainingAccounts(recipient_accounts)
+        .signers([game_a])
+        .rpc();
+
+      const balances_after_tx = await Promise.all(
+        [player_b_ata, player_c_ata, admin_ata].map(
+          async (ata) =>
+            (await connection.getTokenAccountBalance(ata)).value.amount
+        )
+      );
+
+      [...shares, protocol_share].forEach((share, k) =>
+        assert.strictEqual(
+          balances_after_tx[k],
+          new BN(balances_before_tx[k]).add(share).toString()
+        )
+      );
+
+      const config_data = await getConfigData(
+        program,
+        game_a,
+        PROGRAM_CONFIG_SEED
+      );
+
+      assert.deepEqual(
+        config_data.feeRecipients.map((recipient) =>
+          recipient.totalPaid.toString()
+        ),
+        shares.map((share) => share.toString())
+      );
+
+      // later sessions settle their whole fee to the protocol again
+      await program.methods
+        .setFeeRecipients([])
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    });
+  });
+  describe("referrals", () => {
+    const SEED_REFERRED = new BN(25252525);
+
+    const game_a_game_session_25252525_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_REFERRED.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const SEED_FFA_REFERRED = new BN(25253535);
+
+    const game_a_ffa_session_25253535_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("ffa_session"),
+          game_a.publicKey.toBuffer(),
+          SEED_FFA_REFERRED.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const [admin_ata, player_c_ata] = [admin, player_c].map((owner) =>
+      getAssociatedTokenAddressSync(
+        created_mint_a_account.address,
+        owner.publicKey,
+        true,
+        TOKEN_PROGRAM_ID
+      )
+    );
+
+    const REFERRAL_FEE = new BN(5000);
+
+    it("should throw if the referral basis points are too high", async () => {
+      try {
+        await program.methods
+          .setReferralFee(new BN(10001))
+          .accounts({
+            authority: admin.publicKey,
+            game: game_a.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Referral basis points too high");
+      }
+    });
+    it("create game session", async () => {
+      await program.methods
+        .setReferralFee(REFERRAL_FEE)
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      await program.methods
+        .initializeSession(
+          SEED_REFERRED,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_b.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+    });
+    it("should throw if a player refers themselves", async () => {
+      try {
+        await program.methods
+          .deposit(null, player_a.publicKey)
+          .accountsPartial({
+            player: player_a.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_25252525_address,
+          })
+          .signers([player_a])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Players cannot refer themselves");
+      }
+    });
+    it("should store the referrer on the player's seat", async () => {
+      await program.methods
+        .deposit(null, player_c.publicKey)
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_25252525_address,
+        })
+        .signers([player_a])
+        .rpc();
+
+      await program.methods
+        .deposit(null, null)
+        .accountsPartial({
+          player: player_b.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_25252525_address,
+        })
+        .signers([player_b])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_REFERRED
+      );
+
+      assert.strictEqual(
+        game_session.teams[0][0].referrer.toString(),
+        player_c.publicKey.toString()
+      );
+      assert.isNull(game_session.teams[1][0].referrer);
+    });
+    it("should throw if the referrer claims before the session is settled", async () => {
+      try {
+        await program.methods
+          .claimReferral()
+          .accountsPartial({
+            referrer: player_c.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_25252525_address,
+          })
+          .signers([player_c])
+          .rpc();
+        assert.fail("claiming before settlement should fail");
+      } catch (error) {
+        expect(error.message).to.contain("Session is not settled");
+      }
+    });
+    it("should throw if the session closes before its referrals are claimed", async () => {
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_25252525_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_25252525_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_25252525_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .payout()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_25252525_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      try {
+        await program.methods
+          .close()
+          .accountsPartial({
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_25252525_address,
+          })
+          .signers([game_a])
+          .rpc();
+        assert.fail("closing with unclaimed referrals should fail");
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Cannot close, referrals not claimed before the settlement deadline"
+        );
+      }
+    });
+    it("should let the referrer claim their share of the fee", async () => {
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_REFERRED
+      );
+
+      // player_a staked half of the pot, so half of the fee is theirs to share
+      const referral = game_session.terminationFee
+        .divn(2)
+        .mul(REFERRAL_FEE)
+        .divn(10000);
+
+      const [referrer_balance_before_tx, admin_balance_before_tx] =
+        await Promise.all(
+          [player_c_ata, admin_ata].map(
+            async (ata) =>
+              (await connection.getTokenAccountBalance(ata)).value.amount
+          )
+        );
+
+      await program.methods
+        .claimReferral()
+        .accountsPartial({
+          referrer: player_c.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_25252525_address,
+        })
+        .signers([player_c])
+        .rpc();
+
+      try {
+        await program.methods
+          .claimReferral()
+          .accountsPartial({
+            referrer: player_c.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_25252525_address,
+          })
+          .signers([player_c])
+          .rpc();
+        assert.fail("claiming twice should fail");
+      } catch (error) {
+        expect(error.message).to.contain("No referrals left to claim");
+      }
+
+      // the close only needs the session's own accounts
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_25252525_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const [referrer_balance_after_tx, admin_balance_after_tx] =
+        await Promise.all(
+          [player_c_ata, admin_ata].map(
+            async (ata) =>
+              (await connection.getTokenAccountBalance(ata)).value.amount
+          )
+        );
+
+      assert.strictEqual(
+        referrer_balance_after_tx,
+        new BN(referrer_balance_before_tx).add(referral).toString()
+      );
+      assert.strictEqual(
+        admin_balance_after_tx,
+        new BN(admin_balance_before_tx)
+          .add(game_session.terminationFee)
+          .sub(referral)
+          .toString()
+      );
+    });
+    it("should let the referrers of a free-for-all session claim the same way", async () => {
+      await program.methods
+        .initializeFfaSession(
+          SEED_FFA_REFERRED,
+          new BN(100).mul(ONE_TOKEN),
+          2,
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          [10000]
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+      for (const [player, referrer] of [
+        [player_a, player_c.publicKey],
+        [player_b, null],
+      ] as [anchor.web3.Keypair, PublicKey | null][]) {
+        await program.methods
+          .joinFfaSession(referrer)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            ffaSession: game_a_ffa_session_25253535_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_25253535_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_25253535_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareFfaPlacements(Buffer.from([1]))
+        .accountsPartial({
+          game: game_a.publicKey,
+          ffaSession: game_a_ffa_session_25253535_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .payoutFfa()
+        .accountsPartial({
+          player: player_b.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          ffaSession: game_a_ffa_session_25253535_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const ffa_session = await getFfaSessionData(
+        program,
+        game_a,
+        "ffa_session",
+        SEED_FFA_REFERRED
+      );
+
+      // player_a staked half of the pot, so half of the fee is theirs to share
+      const referral = ffa_session.terminationFee
+        .divn(2)
+        .mul(REFERRAL_FEE)
+        .divn(10000);
+
+      const referrer_balance_before_tx = (
+        await connection.getTokenAccountBalance(player_c_ata)
+      ).value.amount;
+
+      await program.methods
+        .claimFfaReferral()
+        .accountsPartial({
+          referrer: player_c.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          ffaSession: game_a_ffa_session_25253535_address,
+        })
+        .signers([player_c])
+        .rpc();
+
+      await program.methods
+        .closeFfaSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          ffaSession: game_a_ffa_session_25253535_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const referrer_balance_after_tx = (
+        await connection.getTokenAccountBalance(player_c_ata)
+      ).value.amount;
+
+      assert.strictEqual(
+        referrer_balance_after_tx,
+        new BN(referrer_balance_before_tx).add(referral).toString()
+      );
+
+      await program.methods
+        .setReferralFee(new BN(0))
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    });
+  });
+  describe("fee schedule", () => {
+    const SEED_MIN_FEE = new BN(26262626);
+    const SEED_TIER_FEE = new BN(27272727);
+    const SEED_MAX_FEE = new BN(28282828);
+
+    const MIN_FEE = ONE_TOKEN;
+    const MAX_FEE = new BN(30).mul(ONE_TOKEN);
+    const FEE_TIERS = [
+      { minPot: new BN(1000).mul(ONE_TOKEN), basisPoints: new BN(100) },
+    ];
+
+    const initializeSession = (seed: BN, entry_cost_per_team: BN) =>
+      program.methods
+        .initializeSession(
+          seed,
+          entry_cost_per_team,
+          [[player_a.publicKey], [player_b.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([game_a])
+        .rpc();
+
+    it("should throw if the tiers are not sorted by pot size", async () => {
+      try {
+        await program.methods
+          .updateFeeSchedule(MIN_FEE, MAX_FEE, [
+            { minPot: new BN(1000).mul(ONE_TOKEN), basisPoints: new BN(100) },
+            { minPot: new BN(500).mul(ONE_TOKEN), basisPoints: new BN(150) },
+          ])
+          .accounts({
+            authority: admin.publicKey,
+            game: game_a.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain(
+          "Fee tiers must be sorted by pot size, each below 10000 basis points with the jackpot share"
+        );
+      }
+    });
+    it("should throw if the minimum fee exceeds the maximum fee", async () => {
+      try {
+        await program.methods
+          .updateFeeSchedule(MAX_FEE, MIN_FEE, FEE_TIERS)
+          .accounts({
+            authority: admin.publicKey,
+            game: game_a.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+      } catch (error) {
+        expect(error.message).to.contain("Minimum fee exceeds maximum fee");
+      }
+    });
+    it("should throw instead of overflowing on a huge tier rate", async () => {
+      // the tier rate only overflows once added to a jackpot share
+      await program.methods
+        .updateProgramConfig(new BN(200), admin.publicKey, new BN(100))
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      try {
+        await program.methods
+          .updateFeeSchedule(MIN_FEE, MAX_FEE, [
+            {
+              minPot: new BN(1000).mul(ONE_TOKEN),
+              basisPoints: new BN("18446744073709551615"),
+            },
+          ])
+          .accounts({
+            authority: admin.publicKey,
+            game: game_a.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+        assert.fail("a tier rate overflowing u64 should fail");
+      } catch (error) {
+        expect(error.message).to.contain("Fee tiers must be sorted by pot size");
+      }
+
+      await program.methods
+        .updateProgramConfig(new BN(200), admin.publicKey, new BN(0))
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    });
+    it("should raise the fee of a micro stake session to the minimum", async () => {
+      await program.methods
+        .updateFeeSchedule(MIN_FEE, MAX_FEE, FEE_TIERS)
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      // 2% of a 20 token pot is below the minimum
+      await initializeSession(SEED_MIN_FEE, new BN(10).mul(ONE_TOKEN));
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_MIN_FEE
+      );
+
+      assert.strictEqual(
+        game_session.terminationFee.toString(),
+        MIN_FEE.toString()
+      );
+    });
+    it("should charge the rate of the pot's tier", async () => {
+      await initializeSession(SEED_TIER_FEE, new BN(1000).mul(ONE_TOKEN));
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_TIER_FEE
+      );
+
+      // 1% of a 2000 token pot
+      assert.strictEqual(
+        game_session.terminationFee.toString(),
+        new BN(20).mul(ONE_TOKEN).toString()
+      );
+    });
+    it("should cap the fee of a high roller session", async () => {
+      await initializeSession(SEED_MAX_FEE, new BN(4000).mul(ONE_TOKEN));
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_MAX_FEE
+      );
+
+      assert.strictEqual(
+        game_session.terminationFee.toString(),
+        MAX_FEE.toString()
+      );
+
+      await program.methods
+        .updateFeeSchedule(new BN(0), new BN(0), [])
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    });
+  });
+  describe("fee from pot", () => {
+    const SEED_FEE_FROM_POT = new BN(29292929);
+
+    const game_a_game_session_29292929_address =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(GAME_SESSION_SEED),
+          game_a.publicKey.toBuffer(),
+          SEED_FEE_FROM_POT.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    const [admin_ata, game_ata, player_a_ata] = [admin, game_a, player_a].map(
+      (owner) =>
+        getAssociatedTokenAddressSync(
+          created_mint_a_account.address,
+          owner.publicKey,
+          true,
+          TOKEN_PROGRAM_ID
+        )
+    );
+
+    let game_balance_before_session: string;
+
+    it("create game session without pre-funding the fee", async () => {
+      await program.methods
+        .setFeeFromPot(true)
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      game_balance_before_session = (
+        await connection.getTokenAccountBalance(game_ata)
+      ).value.amount;
+
+      await program.methods
+        .initializeSession(
+          SEED_FEE_FROM_POT,
+          new BN(100).mul(ONE_TOKEN),
+          [[player_a.publicKey], [player_b.publicKey]],
+          JOIN_DEADLINE,
+          SETTLEMENT_DEADLINE,
+          false,
+          [10000],
+          false,
+          false
+        )
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameAta: null,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_FEE_FROM_POT
+      );
+
+      assert.isFalse(game_session.terminationFeePaid);
+      assert.strictEqual(game_session.vaultBalance.toString(), "0");
+    });
+    it("should take the fee from the prize pool on settlement", async () => {
+      for (const player of [player_a, player_b]) {
+        await program.methods
+          .deposit(null, null)
+          .accountsPartial({
+            player: player.publicKey,
+            game: game_a.publicKey,
+            mint: created_mint_a_account.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            gameSession: game_a_game_session_29292929_address,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_29292929_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .endSession()
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_29292929_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .declareResult({ winner: { team: 0 } })
+        .accountsPartial({
+          game: game_a.publicKey,
+          gameSession: game_a_game_session_29292929_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const game_session = await getGameSessionData(
+        program,
+        game_a,
+        GAME_SESSION_SEED,
+        SEED_FEE_FROM_POT
+      );
+
+      const winnings = game_session.sessionEntryCostPerTeam
+        .muln(game_session.amountOfTeams)
+        .sub(game_session.terminationFee);
+
+      const [player_balance_before_tx, admin_balance_before_tx] =
+        await Promise.all(
+          [player_a_ata, admin_ata].map(
+            async (ata) =>
+              (await connection.getTokenAccountBalance(ata)).value.amount
+          )
+        );
+
+      await program.methods
+        .payout()
+        .accountsPartial({
+          player: player_a.publicKey,
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_29292929_address,
+        })
+        .signers([game_a])
+        .rpc();
+
+      await program.methods
+        .close()
+        .accountsPartial({
+          game: game_a.publicKey,
+          mint: created_mint_a_account.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          gameSession: game_a_game_session_29292929_address,
+          gameAta: null,
+        })
+        .signers([game_a])
+        .rpc();
+
+      const [player_balance_after_tx, admin_balance_after_tx, game_balance] =
+        await Promise.all(
+          [player_a_ata, admin_ata, game_ata].map(
+            async (ata) =>
+              (await connection.getTokenAccountBalance(ata)).value.amount
+          )
+        );
+
+      assert.strictEqual(
+        player_balance_after_tx,
+        new BN(player_balance_before_tx).add(winnings).toString()
+      );
+      assert.strictEqual(
+        admin_balance_after_tx,
+        new BN(admin_balance_before_tx)
+          .add(game_session.terminationFee)
+          .toString()
+      );
+      // the game never held any of the session's tokens
+      assert.strictEqual(game_balance, game_balance_before_session);
+
+      await program.methods
+        .setFeeFromPot(false)
+        .accounts({
+          authority: admin.publicKey,
+          game: game_a.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+    });
+  });
 });